@@ -0,0 +1,119 @@
+//! Command-line parsing shared by the puzzle binaries.
+//!
+//! Each binary keeps its own `Options` and `USAGE`, and walks its arguments
+//! with [`Args`]:
+//!
+//! ```
+//! use aoc_args::Args;
+//!
+//! fn parse_args(args: &[String]) -> Result<(bool, u32), String> {
+//!     let (mut verbose, mut count) = (false, 1);
+//!     let mut args = Args::new(args);
+//!     while let Some(arg) = args.next() {
+//!         match arg {
+//!             "--verbose" => verbose = true,
+//!             "--count" => count = args.parse(arg)?,
+//!             _ => return Err(aoc_args::unknown(arg)),
+//!         }
+//!     }
+//!     Ok((verbose, count))
+//! }
+//!
+//! let args = ["--count".to_string(), "3".to_string()];
+//! assert_eq!(Ok((false, 3)), parse_args(&args));
+//! ```
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, process, slice};
+
+/// The arguments the program was run with, without its name.
+pub fn from_env() -> Vec<String> {
+    env::args().skip(1).collect()
+}
+
+/// Prints `err` and `usage` and exits with status 2.
+pub fn usage_error(err: impl Display, usage: &str) -> ! {
+    eprintln!("{}\n\n{}", err, usage.trim_end());
+    process::exit(2);
+}
+
+/// The error for an option the program doesn't know.
+pub fn unknown(arg: &str) -> String {
+    format!("unknown option {}", arg)
+}
+
+/// Arguments read one at a time, where an option's value is the argument
+/// after it.
+pub struct Args<'a> {
+    args: slice::Iter<'a, String>,
+}
+
+impl<'a> Args<'a> {
+    pub fn new(args: &'a [String]) -> Self {
+        Args { args: args.iter() }
+    }
+
+    /// The value following `option`.
+    pub fn value(&mut self, option: &str) -> Result<&'a str, String> {
+        self.next()
+            .ok_or_else(|| format!("{} needs a value", option))
+    }
+
+    /// The value following `option`, parsed as a `T`.
+    pub fn parse<T: FromStr>(&mut self, option: &str) -> Result<T, String> {
+        let value = self.value(option)?;
+        value
+            .parse()
+            .map_err(|_| format!("invalid value {} for {}", value, option))
+    }
+}
+
+impl<'a> Iterator for Args<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.args.next().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn value_works() {
+        let args = strings(&["--image", "out.png", "--image"]);
+        let mut args = Args::new(&args);
+        assert_eq!(Some("--image"), args.next());
+        assert_eq!(Ok("out.png"), args.value("--image"));
+        assert_eq!(Some("--image"), args.next());
+        assert_eq!(
+            Err("--image needs a value".to_string()),
+            args.value("--image")
+        );
+    }
+
+    #[test]
+    fn parse_works() {
+        let args = strings(&["3", "x", "-1"]);
+        let mut args = Args::new(&args);
+        assert_eq!(Ok(3), args.parse::<usize>("--knots"));
+        assert_eq!(
+            Err("invalid value x for --knots".to_string()),
+            args.parse::<usize>("--knots")
+        );
+        assert_eq!(
+            Err("invalid value -1 for --knots".to_string()),
+            args.parse::<usize>("--knots")
+        );
+        assert_eq!(
+            Err("--knots needs a value".to_string()),
+            args.parse::<usize>("--knots")
+        );
+    }
+}
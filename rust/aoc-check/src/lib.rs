@@ -0,0 +1,150 @@
+//! Randomised checks for the puzzle crates' tests, without external dependencies.
+//!
+//! [`check`] runs a property against a fixed series of seeds, so a failure
+//! shows up on every run and can be replayed with [`Rng::new`].
+
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Number of cases [`check`] runs.
+pub const CASES: u64 = 256;
+
+/// Runs `property` for [`CASES`] cases, each with its own deterministic [`Rng`].
+///
+/// A failing case is reported with its seed before the panic carries on.
+pub fn check(mut property: impl FnMut(&mut Rng)) {
+    for case in 0..CASES {
+        let seed = Rng::new(case).next_u64();
+        let mut rng = Rng::new(seed);
+        if let Err(panic) = panic::catch_unwind(AssertUnwindSafe(|| property(&mut rng))) {
+            eprintln!("case {} failed, replay with Rng::new({:#x})", case, seed);
+            panic::resume_unwind(panic);
+        }
+    }
+}
+
+/// A splitmix64 generator with helpers for building test inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Any `u32`, half of the time one of the edge values `0`, `1` or `u32::MAX`.
+    pub fn u32(&mut self) -> u32 {
+        match self.next_u64() % 6 {
+            0 => 0,
+            1 => 1,
+            2 => u32::MAX,
+            _ => self.next_u64() as u32,
+        }
+    }
+
+    /// A value picked uniformly from `range`, which must not be empty.
+    pub fn range<T: Int>(&mut self, range: Range<T>) -> T {
+        let (start, end) = (range.start.to_u64(), range.end.to_u64());
+        assert!(start < end, "empty range");
+        T::from_u64(start + self.next_u64() % (end - start))
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+
+    /// A string of `len` characters taken from `alphabet`.
+    pub fn string(&mut self, alphabet: &str, len: Range<usize>) -> String {
+        let chars: Vec<char> = alphabet.chars().collect();
+        let len = self.range(len);
+        (0..len)
+            .map(|_| chars[self.range(0..chars.len())])
+            .collect()
+    }
+
+    /// A vector of `len` items made by `item`.
+    pub fn vec<T>(&mut self, len: Range<usize>, mut item: impl FnMut(&mut Rng) -> T) -> Vec<T> {
+        let len = self.range(len);
+        (0..len).map(|_| item(self)).collect()
+    }
+}
+
+/// Integers [`Rng::range`] can pick from.
+pub trait Int: Copy {
+    fn to_u64(self) -> u64;
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            fn from_u64(value: u64) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+int!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!(a.vec(3..4, Rng::u32), b.vec(3..4, Rng::u32));
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn range_stays_in_bounds() {
+        let mut rng = Rng::new(0);
+        let values: Vec<u8> = (0..1000).map(|_| rng.range(3..7)).collect();
+        assert!(values.iter().all(|value| (3..7).contains(value)));
+        for value in 3..7 {
+            assert!(values.contains(&value));
+        }
+        assert_eq!(u64::MAX - 1, rng.range(u64::MAX - 1..u64::MAX));
+    }
+
+    #[test]
+    fn string_works() {
+        let mut rng = Rng::new(1);
+        for _ in 0..100 {
+            let text = rng.string("ab", 2..5);
+            assert!((2..5).contains(&text.len()));
+            assert!(text.chars().all(|c| c == 'a' || c == 'b'));
+        }
+    }
+
+    #[test]
+    fn check_runs_every_case() {
+        let mut runs = 0;
+        check(|_| runs += 1);
+        assert_eq!(CASES, runs);
+    }
+
+    #[test]
+    #[should_panic(expected = "odd")]
+    fn check_passes_on_failures() {
+        check(|rng| assert!(rng.next_u64() % 2 == 0, "odd"));
+    }
+}
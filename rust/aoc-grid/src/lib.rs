@@ -0,0 +1,452 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Sub};
+
+/// Position of a cell inside a [`Grid`], as `(x, y)` with `y` growing downwards.
+pub type Pos = (usize, usize);
+
+/// A point on the unbounded plane, using the same axes as [`Grid`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /// Componentwise sign, i.e. a step of at most one in each axis towards `self`.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Distance when diagonal steps are allowed (king moves).
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Distance when only orthogonal steps are allowed.
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((x, y): Pos) -> Self {
+        Point::new(x as i32, y as i32)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, dir: Direction) -> Point {
+        self + dir.delta()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, dir: Direction) {
+        *self = *self + dir;
+    }
+}
+
+/// One of the eight compass directions. `Up` is towards row 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise from `Up`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All eight directions, clockwise from `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+            Direction::UpLeft => Point::new(-1, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::DownRight => Point::new(1, 1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let delta = self.delta();
+        delta.x != 0 && delta.y != 0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line has a different length from the first line.
+    Ragged {
+        y: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell conversion rejected a character.
+    InvalidChar { pos: Pos, c: char },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Ragged { y, expected, found } => write!(
+                f,
+                "line {} has {} cells, expected {}",
+                y + 1,
+                found,
+                expected
+            ),
+            ParseError::InvalidChar { pos: (x, y), c } => {
+                write!(
+                    f,
+                    "invalid character {:?} at line {}, column {}",
+                    c,
+                    y + 1,
+                    x + 1
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A rectangular grid stored row-major in a single `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from row-major cells. Panics if `cells` doesn't fill whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            cells.len().is_multiple_of(width),
+            "{} cells do not fill rows of width {}",
+            cells.len(),
+            width
+        );
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses one cell per character, one row per line. `convert` returning `None`
+    /// rejects the character.
+    pub fn from_chars<F>(input: &str, mut convert: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(Pos, char) -> Option<T>,
+    {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let cell = convert((x, y), c).ok_or(ParseError::InvalidChar { pos: (x, y), c })?;
+                cells.push(cell);
+            }
+            let found = cells.len() - row_start;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseError::Ragged { y, expected, found });
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Converts a signed point to a position if it lies inside the grid.
+    pub fn pos(&self, point: Point) -> Option<Pos> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        self.index_of((x, y)).map(|_| (x, y))
+    }
+
+    /// The neighbouring position in `dir`, if it is inside the grid.
+    pub fn step(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        self.pos(Point::from(pos) + dir)
+    }
+
+    /// Orthogonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Orthogonal and diagonal neighbours of `pos` that lie inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Cells walking from `pos` (exclusive) in `dir` until the edge of the grid.
+    pub fn ray(&self, pos: Pos, dir: Direction) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        std::iter::successors(self.step(pos, dir), move |&pos| self.step(pos, dir))
+            .map(move |pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(y < self.height, "row {} out of bounds", y);
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // `max(1)` so an empty grid yields no rows instead of panicking
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", pos))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "123
+456";
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::from_chars(input, |_, c| c.to_digit(10))
+    }
+
+    #[test]
+    fn from_chars_works() {
+        let grid = digits(INPUT).unwrap();
+        assert_eq!(Grid::from_vec(3, vec![1, 2, 3, 4, 5, 6]), grid);
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                pos: (1, 1),
+                c: 'x'
+            }),
+            digits("123\n4x6")
+        );
+        assert_eq!(
+            Err(ParseError::Ragged {
+                y: 1,
+                expected: 3,
+                found: 2
+            }),
+            digits("123\n45")
+        );
+        assert_eq!(Ok(Grid::from_vec(0, vec![])), digits(""));
+    }
+
+    #[test]
+    fn get_works() {
+        let grid = digits(INPUT).unwrap();
+        assert_eq!(Some(&6), grid.get((2, 1)));
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get((0, 2)));
+        assert_eq!(Some((0, 1)), grid.pos(Point::new(0, 1)));
+        assert_eq!(None, grid.pos(Point::new(-1, 0)));
+        assert_eq!(4, grid[(0, 1)]);
+    }
+
+    #[test]
+    fn neighbours_work() {
+        let grid = digits(INPUT).unwrap();
+        assert_eq!(
+            vec![(1, 0), (2, 1), (0, 1)],
+            grid.neighbours4((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 0), (1, 1), (0, 1)],
+            grid.neighbours8((0, 0)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iterators_work() {
+        let grid = digits(INPUT).unwrap();
+        assert_eq!(vec![&4, &5, &6], grid.row(1).collect::<Vec<_>>());
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            vec![((1, 0), &2), ((0, 0), &1)],
+            grid.ray((2, 0), Direction::Left).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![((1, 0), &2)],
+            grid.ray((0, 1), Direction::UpRight).collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.ray((0, 0), Direction::Up).count());
+        assert_eq!(Some(((2, 1), &6)), grid.iter().last());
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 out of bounds")]
+    fn row_checks_bounds() {
+        digits(INPUT).unwrap().row(2).count();
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!(INPUT, digits(INPUT).unwrap().to_string());
+    }
+
+    #[test]
+    fn point_works() {
+        let a = Point::new(3, -1);
+        let b = Point::new(1, 2);
+        assert_eq!(Point::new(1, -1), (a - b).signum());
+        assert_eq!(3, a.chebyshev(b));
+        assert_eq!(5, a.manhattan(b));
+        assert_eq!(Point::new(4, -2), a + Direction::UpRight);
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        name: "dirs",
+        default: 180,
+        help: "directories besides /",
+    },
+    Knob {
+        name: "files",
+        default: 280,
+        help: "files, plus one in / if needed to fill the disk",
+    },
+];
+
+const DISK: u32 = 70_000_000;
+/// Part 2 frees up to 30000000 of the 70000000, so it needs more than this
+/// in use.
+const MIN_USED: u32 = 40_000_001;
+/// The files stay under this in total, leaving room to pad up to `MIN_USED`.
+const MAX_FILES: u32 = 60_000_000;
+/// Most directories or files a transcript can have, well below `MAX_FILES`
+/// so each file can be at least a few bytes.
+const MAX_COUNT: usize = 1_000_000;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    subdirs: Vec<usize>,
+    files: Vec<(String, u32)>,
+    /// Files and directories share a namespace.
+    names: HashSet<String>,
+}
+
+impl Dir {
+    fn is_empty(&self) -> bool {
+        self.subdirs.is_empty() && self.files.is_empty()
+    }
+}
+
+/// A lowercase name not yet in `names`, sometimes with an extension.
+fn fresh_name(rng: &mut ChaCha8Rng, names: &mut HashSet<String>, extension: bool) -> String {
+    let word = |rng: &mut ChaCha8Rng, max| {
+        let len = rng.gen_range(1..=max);
+        (0..len)
+            .map(|_| rng.gen_range('a'..='z'))
+            .collect::<String>()
+    };
+    loop {
+        let mut name = word(rng, 8);
+        if extension && rng.gen_bool(0.5) {
+            name.push('.');
+            name.push_str(&word(rng, 3));
+        }
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// A random tree with each file in a random directory. Between them the files
+/// use enough of the disk for part 2 to need to delete something.
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let (dirs, files) = (sizes.get("dirs"), sizes.get("files"));
+    if dirs > MAX_COUNT || files > MAX_COUNT {
+        return Err(Error::InvalidSizes(format!(
+            "there can be at most {} directories and {} files",
+            MAX_COUNT, MAX_COUNT
+        )));
+    }
+    let mut tree = vec![Dir::default()];
+    for idx in 1..=dirs {
+        let parent = rng.gen_range(0..idx);
+        let name = fresh_name(rng, &mut tree[parent].names, false);
+        tree.push(Dir {
+            name,
+            ..Dir::default()
+        });
+        tree[parent].subdirs.push(idx);
+    }
+
+    let max_size = (MAX_FILES / files.max(1) as u32).min(300_000);
+    let mut used = 0;
+    for _ in 0..files {
+        let dir = &mut tree[rng.gen_range(0..=dirs)];
+        let name = fresh_name(rng, &mut dir.names, true);
+        let size = rng.gen_range(1..=max_size);
+        used += size;
+        dir.files.push((name, size));
+    }
+    let target = rng.gen_range(used.max(MIN_USED)..DISK);
+    if target > used {
+        let name = fresh_name(rng, &mut tree[0].names, true);
+        tree[0].files.push((name, target - used));
+    }
+
+    let mut transcript = vec!["$ cd /".to_string()];
+    explore(&tree, 0, &mut transcript);
+    // like the puzzle, stop after the last listing
+    while transcript.last().is_some_and(|line| line == "$ cd ..") {
+        transcript.pop();
+    }
+    Ok(transcript.join("\n") + "\n")
+}
+
+/// Lists `tree[idx]` and then explores each subdirectory with something in
+/// it, in the order they were listed.
+fn explore(tree: &[Dir], idx: usize, transcript: &mut Vec<String>) {
+    let dir = &tree[idx];
+    let mut listing = dir
+        .subdirs
+        .iter()
+        .map(|&sub| (tree[sub].name.as_str(), format!("dir {}", tree[sub].name)))
+        .chain(
+            dir.files
+                .iter()
+                .map(|(name, size)| (name.as_str(), format!("{} {}", size, name))),
+        )
+        .collect::<Vec<_>>();
+    listing.sort();
+    transcript.push("$ ls".to_string());
+    transcript.extend(listing.into_iter().map(|(_, line)| line));
+
+    let mut subdirs = dir.subdirs.clone();
+    subdirs.sort_by_key(|&sub| &tree[sub].name);
+    for sub in subdirs {
+        // an empty directory's `ls` would print nothing, which the solution
+        // doesn't parse
+        if !tree[sub].is_empty() {
+            transcript.push(format!("$ cd {}", tree[sub].name));
+            explore(tree, sub, transcript);
+            transcript.push("$ cd ..".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{generator, Error};
+
+    fn transcript(seed: u64, dirs: usize, files: usize) -> String {
+        let overrides = [("dirs".to_string(), dirs), ("files".to_string(), files)];
+        generator(7).unwrap().generate(seed, &overrides).unwrap()
+    }
+
+    #[test]
+    fn fills_the_disk_for_part2() {
+        for (seed, dirs, files) in [(1, 180, 280), (2, 0, 0), (3, 5, 1000), (4, 50, 3)] {
+            let transcript = transcript(seed, dirs, files);
+            let used = transcript
+                .lines()
+                .filter_map(|line| line.split_once(' ')?.0.parse::<u32>().ok())
+                .sum::<u32>();
+            assert!((40_000_001..70_000_000).contains(&used), "{}", used);
+        }
+    }
+
+    #[test]
+    fn bounds_counts() {
+        let generator = generator(7).unwrap();
+        for knob in ["dirs", "files"] {
+            let overrides = [(knob.to_string(), 1_000_001)];
+            assert!(matches!(
+                generator.generate(1, &overrides),
+                Err(Error::InvalidSizes(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn solvable_by_day_07() {
+        for seed in 0..10 {
+            let transcript = transcript(seed, 30, 60);
+            let (rest, commands) = day_07::commands(&transcript).unwrap();
+            assert_eq!("\n", rest);
+            assert_eq!(transcript.matches("$ ").count(), commands.len());
+            // part 2 panics if there's nothing big enough to delete
+            assert!(day_07::part2(&transcript) > 0);
+        }
+    }
+
+    #[test]
+    fn only_enters_listed_directories() {
+        let transcript = transcript(5, 40, 20);
+        let mut stack = vec![vec![]];
+        let lines = transcript.lines().skip(1).collect::<Vec<_>>();
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(name) = line.strip_prefix("$ cd ") {
+                if name == ".." {
+                    stack.pop();
+                } else {
+                    assert!(stack.last().unwrap().contains(&format!("dir {}", name)));
+                    assert_eq!(Some(&"$ ls"), lines.get(idx + 1));
+                    stack.push(vec![]);
+                }
+            } else if *line != "$ ls" {
+                stack.last_mut().unwrap().push(line.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{lines, Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        name: "width",
+        default: 99,
+        help: "trees per row",
+    },
+    Knob {
+        name: "height",
+        default: 99,
+        help: "rows",
+    },
+];
+
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let (width, height) = (sizes.get("width"), sizes.get("height"));
+    if width == 0 || height == 0 {
+        return Err(Error::InvalidSizes(
+            "a forest needs at least one tree".to_string(),
+        ));
+    }
+    Ok(lines(height, || {
+        (0..width).map(|_| rng.gen_range('0'..='9')).collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator;
+
+    #[test]
+    fn solution_parses_it() {
+        for (seed, width, height) in [(1, 99, 99), (2, 1, 1), (3, 7, 2), (4, 3, 12)] {
+            let overrides = [("width".to_string(), width), ("height".to_string(), height)];
+            let input = generator(8).unwrap().generate(seed, &overrides).unwrap();
+            // every tree on the edge is visible, and only those have a
+            // scenic score of 0
+            let edge = width * height - width.saturating_sub(2) * height.saturating_sub(2);
+            assert!((edge..=width * height).contains(&day_08::part1(&input)));
+            assert_eq!(edge == width * height, day_08::part2(&input) == 0);
+        }
+    }
+
+    #[test]
+    fn rejects_empty_forests() {
+        let overrides = [("width".to_string(), 0)];
+        assert!(generator(8).unwrap().generate(1, &overrides).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{lines, Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        name: "moves",
+        default: 2000,
+        help: "lines",
+    },
+    Knob {
+        name: "steps",
+        default: 19,
+        help: "most steps in one move",
+    },
+];
+
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let (moves, steps) = (sizes.get("moves"), sizes.get("steps"));
+    if steps == 0 {
+        return Err(Error::InvalidSizes(
+            "moves take at least one step".to_string(),
+        ));
+    }
+    Ok(lines(moves, || {
+        let dir = ["U", "D", "L", "R"].choose(rng).unwrap();
+        format!("{} {}", dir, rng.gen_range(1..=steps))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator;
+
+    #[test]
+    fn solution_parses_it() {
+        let overrides = [("moves".to_string(), 300), ("steps".to_string(), 4)];
+        let input = generator(9).unwrap().generate(3, &overrides).unwrap();
+        let moves = day_09::parse_moves(&input).unwrap();
+        assert_eq!(300, moves.len());
+        assert!(moves.iter().all(|mov| (1..=4).contains(&mov.count)));
+        assert!(day_09::part2(&moves) <= day_09::part1(&moves));
+    }
+}
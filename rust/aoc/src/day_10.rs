@@ -0,0 +1,77 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[Knob {
+    name: "cycles",
+    default: 240,
+    help: "cycles the program runs for",
+}];
+
+/// The CRT is 40 pixels wide.
+const WIDTH: i32 = 40;
+
+/// A program taking exactly `cycles` cycles, whose `addx`s always move the
+/// sprite to somewhere on the screen.
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let mut cycles = sizes.get("cycles");
+    if cycles == 0 {
+        // day-10 can't read an empty program
+        return Err(Error::InvalidSizes(
+            "the program runs for at least one cycle".to_string(),
+        ));
+    }
+    let mut x = 1;
+    let mut program = vec![];
+    while cycles > 0 {
+        let target = rng.gen_range(0..WIDTH);
+        if cycles >= 2 && target != x && rng.gen_bool(0.7) {
+            program.push(format!("addx {}", target - x));
+            x = target;
+            cycles -= 2;
+        } else {
+            program.push("noop".to_string());
+            cycles -= 1;
+        }
+    }
+    Ok(program.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator;
+    use day_10::Inst;
+
+    #[test]
+    fn runs_for_the_cycles_asked_for() {
+        for cycles in [1, 2, 240, 1001] {
+            let overrides = [("cycles".to_string(), cycles)];
+            let program = generator(10).unwrap().generate(6, &overrides).unwrap();
+            // its streaming parser fails on a trailing line ending
+            assert!(!program.ends_with('\n'));
+            let (rest, instructions) = day_10::instructions(&program).unwrap();
+            assert_eq!("", rest);
+            let mut x = 1;
+            let mut ran = 0;
+            for inst in instructions {
+                if let Inst::Addx(amount) = inst {
+                    x += amount;
+                    assert!((0..WIDTH).contains(&x));
+                    ran += 2;
+                } else {
+                    ran += 1;
+                }
+            }
+            assert_eq!(cycles, ran);
+            assert_eq!(6, day_10::part2(&program).lines().count());
+        }
+    }
+
+    #[test]
+    fn rejects_empty_programs() {
+        let overrides = [("cycles".to_string(), 0)];
+        assert!(generator(10).unwrap().generate(6, &overrides).is_err());
+    }
+}
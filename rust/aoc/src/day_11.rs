@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        name: "monkeys",
+        default: 8,
+        help: "monkeys, from 3 to 9",
+    },
+    Knob {
+        name: "items",
+        default: 8,
+        help: "most starting items one monkey holds",
+    },
+];
+
+/// Each monkey tests a different prime. Part 2 squares numbers below their
+/// product, which only fits in a u64 for the first nine.
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+/// Attempts at finding monkeys whose part 1 worry levels fit in a u64.
+const ATTEMPTS: usize = 1000;
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
+
+impl Operation {
+    fn apply(self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(val) => old.checked_add(val),
+            Operation::Mul(val) => old.checked_mul(val),
+            Operation::Square => old.checked_mul(old),
+        }
+    }
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// Writes the monkey's notes, given its number, as in the puzzle input.
+struct Notes<'a>(usize, &'a Monkey);
+
+impl fmt::Display for Notes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Notes(idx, monkey) = self;
+        let items = monkey.items.iter().map(u64::to_string).collect::<Vec<_>>();
+        writeln!(f, "Monkey {}:", idx)?;
+        writeln!(f, "  Starting items: {}", items.join(", "))?;
+        match monkey.operation {
+            Operation::Add(val) => writeln!(f, "  Operation: new = old + {}", val)?,
+            Operation::Mul(val) => writeln!(f, "  Operation: new = old * {}", val)?,
+            Operation::Square => writeln!(f, "  Operation: new = old * old")?,
+        }
+        writeln!(f, "  Test: divisible by {}", monkey.divisor)?;
+        writeln!(f, "    If true: throw to monkey {}", monkey.if_true)?;
+        write!(f, "    If false: throw to monkey {}", monkey.if_false)
+    }
+}
+
+fn monkeys(rng: &mut ChaCha8Rng, count: usize, max_items: usize) -> Vec<Monkey> {
+    let mut divisors = PRIMES;
+    divisors.shuffle(rng);
+    // like the puzzle, exactly one monkey squares
+    let squarer = rng.gen_range(0..count);
+    (0..count)
+        .map(|idx| {
+            let items = (0..rng.gen_range(1..=max_items))
+                .map(|_| rng.gen_range(50..100))
+                .collect();
+            let operation = if idx == squarer {
+                Operation::Square
+            } else if rng.gen_bool(0.5) {
+                Operation::Add(rng.gen_range(1..=8))
+            } else {
+                Operation::Mul(rng.gen_range(2..=19))
+            };
+            let if_true = (0..count)
+                .filter(|&other| other != idx)
+                .choose(rng)
+                .unwrap();
+            let if_false = (0..count)
+                .filter(|&other| other != idx && other != if_true)
+                .choose(rng)
+                .unwrap();
+            Monkey {
+                items,
+                operation,
+                divisor: divisors[idx],
+                if_true,
+                if_false,
+            }
+        })
+        .collect()
+}
+
+/// Whether the 20 rounds of part 1, which never reduce worry levels modulo
+/// anything, stay within a u64.
+fn part1_fits(monkeys: &[Monkey]) -> bool {
+    let mut items = monkeys
+        .iter()
+        .map(|monkey| VecDeque::from(monkey.items.clone()))
+        .collect::<Vec<_>>();
+    for _ in 0..20 {
+        for (idx, monkey) in monkeys.iter().enumerate() {
+            while let Some(item) = items[idx].pop_front() {
+                let Some(item) = monkey.operation.apply(item) else {
+                    return false;
+                };
+                let item = item / 3;
+                let target = if item % monkey.divisor == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                items[target].push_back(item);
+            }
+        }
+    }
+    true
+}
+
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let (count, max_items) = (sizes.get("monkeys"), sizes.get("items"));
+    if !(3..=PRIMES.len()).contains(&count) {
+        // fewer than three can't each throw to two others
+        return Err(Error::InvalidSizes(format!(
+            "there must be 3 to {} monkeys",
+            PRIMES.len()
+        )));
+    }
+    if max_items == 0 {
+        return Err(Error::InvalidSizes(
+            "every monkey starts with at least one item".to_string(),
+        ));
+    }
+    for _ in 0..ATTEMPTS {
+        let monkeys = monkeys(rng, count, max_items);
+        if part1_fits(&monkeys) {
+            let notes = monkeys
+                .iter()
+                .enumerate()
+                .map(|(idx, monkey)| Notes(idx, monkey).to_string())
+                .collect::<Vec<_>>();
+            return Ok(notes.join("\n\n") + "\n");
+        }
+    }
+    Err(Error::InvalidSizes(format!(
+        "no monkeys found in {} attempts whose worry levels fit in 64 bits",
+        ATTEMPTS
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator;
+    use rand::SeedableRng;
+
+    #[test]
+    fn writes_notes_like_the_puzzle() {
+        let monkey = Monkey {
+            items: vec![79, 98],
+            operation: Operation::Mul(19),
+            divisor: 23,
+            if_true: 2,
+            if_false: 3,
+        };
+        assert_eq!(
+            "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3",
+            Notes(0, &monkey).to_string()
+        );
+    }
+
+    #[test]
+    fn monkeys_throw_to_two_others() {
+        for seed in 0..20 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let monkeys = monkeys(&mut rng, 3, 2);
+            for (idx, monkey) in monkeys.iter().enumerate() {
+                assert!(monkey.if_true != idx && monkey.if_false != idx);
+                assert_ne!(monkey.if_true, monkey.if_false);
+                assert!((1..=2).contains(&monkey.items.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn solvable_by_day_11() {
+        let generator = generator(11).unwrap();
+        for seed in 0..10 {
+            let notes = generator.generate(seed, &[]).unwrap();
+            assert!(notes.ends_with('\n') && !notes.ends_with("\n\n"));
+            let (rest, monkeys) = day_11::monkeys(&notes).unwrap();
+            assert_eq!("\n", rest);
+            assert_eq!(8, monkeys.len());
+            // both parts panic on throws to missing monkeys or, in debug
+            // builds, on worry levels overflowing
+            assert!(day_11::part1(&notes) > 0);
+            assert!(day_11::part2(&notes) > 0);
+        }
+    }
+
+    #[test]
+    fn checks_monkey_counts() {
+        let generator = generator(11).unwrap();
+        assert!(generator
+            .generate(1, &[("monkeys".to_string(), 2)])
+            .is_err());
+        assert!(generator.generate(1, &[("monkeys".to_string(), 9)]).is_ok());
+        assert!(generator
+            .generate(1, &[("monkeys".to_string(), 10)])
+            .is_err());
+    }
+}
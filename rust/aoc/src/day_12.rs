@@ -0,0 +1,113 @@
+use std::iter;
+
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        name: "width",
+        default: 179,
+        help: "columns",
+    },
+    Knob {
+        name: "height",
+        default: 41,
+        help: "rows",
+    },
+];
+
+/// Steps up from `a` to `z`.
+const CLIMB: usize = 25;
+
+/// Random heights, leaning towards low ones, with a path from `S` to `E`
+/// that climbs one step at a time. Other routes may be shorter.
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let (width, height) = (sizes.get("width"), sizes.get("height"));
+    // the path needs a step for every letter, even between opposite corners
+    if width == 0 || height == 0 || width + height - 2 < CLIMB {
+        return Err(Error::InvalidSizes(format!(
+            "a {}x{} heightmap has no room to climb from a to z; --width plus --height must be at least {}",
+            width,
+            height,
+            CLIMB + 2
+        )));
+    }
+    let mut heights = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| b'a' + rng.gen_range(0..26).min(rng.gen_range(0..26)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut cell = || (rng.gen_range(0..width), rng.gen_range(0..height));
+    let (start, end) = loop {
+        let (start, end) = (cell(), cell());
+        if start.0.abs_diff(end.0) + start.1.abs_diff(end.1) >= CLIMB {
+            break (start, end);
+        }
+    };
+
+    // a shortest route in a random order of horizontal and vertical steps,
+    // climbing a letter on CLIMB of them
+    let step = |from: usize, to: usize| if from < to { 1 } else { -1 };
+    let mut steps = iter::repeat_n((step(start.0, end.0), 0), start.0.abs_diff(end.0))
+        .chain(iter::repeat_n(
+            (0, step(start.1, end.1)),
+            start.1.abs_diff(end.1),
+        ))
+        .collect::<Vec<(isize, isize)>>();
+    steps.shuffle(rng);
+    let mut climbs = vec![false; steps.len()];
+    for idx in index::sample(rng, steps.len(), CLIMB) {
+        climbs[idx] = true;
+    }
+    let (mut x, mut y) = start;
+    let mut letter = b'a';
+    for ((dx, dy), climb) in steps.into_iter().zip(climbs) {
+        x = x.wrapping_add_signed(dx);
+        y = y.wrapping_add_signed(dy);
+        letter += climb as u8;
+        heights[y][x] = letter;
+    }
+    heights[start.1][start.0] = b'S';
+    heights[end.1][end.0] = b'E';
+
+    let rows = heights
+        .into_iter()
+        .map(|row| String::from_utf8(row).unwrap())
+        .collect::<Vec<_>>();
+    Ok(rows.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator;
+
+    fn heightmap(seed: u64, width: usize, height: usize) -> Result<String, crate::Error> {
+        let overrides = [("width".to_string(), width), ("height".to_string(), height)];
+        generator(12).unwrap().generate(seed, &overrides)
+    }
+
+    #[test]
+    fn always_has_a_path() {
+        for (seed, width, height) in [(1, 179, 41), (2, 14, 13), (3, 27, 1), (4, 1, 30)] {
+            let input = heightmap(seed, width, height).unwrap();
+            let heightmap = day_12::parse_heightmap(&input).unwrap();
+            assert_eq!(1, heightmap.starts.len());
+            assert_eq!(1, heightmap.ends.len());
+            let steps = day_12::part1(&heightmap).unwrap();
+            assert!(steps >= 25);
+            assert!(day_12::part2(&heightmap).unwrap() <= steps);
+        }
+    }
+
+    #[test]
+    fn rejects_maps_too_small_to_climb() {
+        assert!(heightmap(1, 13, 13).is_err());
+        assert!(heightmap(1, 0, 40).is_err());
+    }
+}
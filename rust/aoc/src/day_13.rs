@@ -0,0 +1,87 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Error, Knob, Sizes};
+
+pub const KNOBS: &[Knob] = &[
+    Knob {
+        name: "pairs",
+        default: 150,
+        help: "pairs of packets",
+    },
+    Knob {
+        name: "depth",
+        default: 5,
+        help: "most lists nested inside each other, the packet included",
+    },
+    Knob {
+        name: "length",
+        default: 5,
+        help: "most items in one list",
+    },
+];
+
+/// Writes a list of up to `length` items, with lists inside it down to
+/// `depth` levels in all.
+fn list(rng: &mut ChaCha8Rng, depth: usize, length: usize, out: &mut String) {
+    out.push('[');
+    for idx in 0..rng.gen_range(0..=length) {
+        if idx > 0 {
+            out.push(',');
+        }
+        if depth > 1 && rng.gen_bool(0.3) {
+            list(rng, depth - 1, length, out);
+        } else {
+            out.push_str(&rng.gen_range(0..=10).to_string());
+        }
+    }
+    out.push(']');
+}
+
+pub(crate) fn generate(rng: &mut ChaCha8Rng, sizes: &Sizes) -> Result<String, Error> {
+    let (pairs, depth, length) = (sizes.get("pairs"), sizes.get("depth"), sizes.get("length"));
+    if depth == 0 {
+        return Err(Error::InvalidSizes(
+            "every packet is a list, so --depth must be at least 1".to_string(),
+        ));
+    }
+    let mut out = String::new();
+    for idx in 0..pairs {
+        if idx > 0 {
+            out.push_str("\n\n");
+        }
+        list(rng, depth, length, &mut out);
+        out.push('\n');
+        list(rng, depth, length, &mut out);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator;
+
+    #[test]
+    fn solution_parses_it() {
+        let overrides = [("pairs".to_string(), 40), ("depth".to_string(), 3)];
+        let input = generator(13).unwrap().generate(2, &overrides).unwrap();
+        let (rest, pairs) = day_13::pairs(&input).unwrap();
+        assert_eq!(("", 40), (rest, pairs.len()));
+        let depth = input
+            .chars()
+            .scan(0, |open, c| {
+                match c {
+                    '[' => *open += 1,
+                    ']' => *open -= 1,
+                    _ => {}
+                }
+                Some(*open)
+            })
+            .max();
+        assert!(depth <= Some(3));
+        assert_eq!(
+            day_13::part1(&input),
+            day_13::read_ordered_pair_sum(input.as_bytes()).unwrap()
+        );
+    }
+}
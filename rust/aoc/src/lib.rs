@@ -0,0 +1,167 @@
+//! Random puzzle inputs, for stress-testing the solutions beyond the one
+//! `input.txt` each day ships with.
+
+use std::fmt;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_13;
+
+/// A size setting of a generator, given on the command line as `--name N`.
+pub struct Knob {
+    pub name: &'static str,
+    pub default: usize,
+    pub help: &'static str,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    UnknownKnob(String),
+    /// The knobs ask for an input that can't exist or can't be solved.
+    InvalidSizes(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownKnob(name) => write!(f, "unknown knob --{}", name),
+            Error::InvalidSizes(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The knob values for one run: the overrides given, or else the defaults.
+pub(crate) struct Sizes<'a> {
+    knobs: &'static [Knob],
+    overrides: &'a [(String, usize)],
+}
+
+impl Sizes<'_> {
+    fn get(&self, name: &str) -> usize {
+        match self.overrides.iter().rfind(|(knob, _)| knob == name) {
+            Some(&(_, value)) => value,
+            None => {
+                let knob = self.knobs.iter().find(|knob| knob.name == name);
+                knob.expect("generators only ask for their own knobs")
+                    .default
+            }
+        }
+    }
+}
+
+pub struct Generator {
+    pub day: u32,
+    /// What the inputs look like.
+    pub summary: &'static str,
+    pub knobs: &'static [Knob],
+    generate: fn(&mut ChaCha8Rng, &Sizes) -> Result<String, Error>,
+}
+
+impl Generator {
+    /// Generates the text of an `input.txt`, ending with a newline only if the
+    /// puzzle's does, with `overrides` replacing the defaults of the knobs
+    /// they name. For a given `rand` version, the same seed and knobs always
+    /// give the same input on every platform; how `rand` turns ChaCha8's
+    /// output into ranges and choices can change between versions.
+    pub fn generate(&self, seed: u64, overrides: &[(String, usize)]) -> Result<String, Error> {
+        if let Some((name, _)) = overrides
+            .iter()
+            .find(|(name, _)| !self.knobs.iter().any(|knob| knob.name == name))
+        {
+            return Err(Error::UnknownKnob(name.clone()));
+        }
+        let sizes = Sizes {
+            knobs: self.knobs,
+            overrides,
+        };
+        (self.generate)(&mut ChaCha8Rng::seed_from_u64(seed), &sizes)
+    }
+}
+
+pub const GENERATORS: [Generator; 7] = [
+    Generator {
+        day: 7,
+        summary: "shell transcripts exploring a directory tree",
+        knobs: day_07::KNOBS,
+        generate: day_07::generate,
+    },
+    Generator {
+        day: 8,
+        summary: "forests of tree heights",
+        knobs: day_08::KNOBS,
+        generate: day_08::generate,
+    },
+    Generator {
+        day: 9,
+        summary: "rope moves",
+        knobs: day_09::KNOBS,
+        generate: day_09::generate,
+    },
+    Generator {
+        day: 10,
+        summary: "addx/noop programs",
+        knobs: day_10::KNOBS,
+        generate: day_10::generate,
+    },
+    Generator {
+        day: 11,
+        summary: "monkey configurations",
+        knobs: day_11::KNOBS,
+        generate: day_11::generate,
+    },
+    Generator {
+        day: 12,
+        summary: "heightmaps with a path from S to E",
+        knobs: day_12::KNOBS,
+        generate: day_12::generate,
+    },
+    Generator {
+        day: 13,
+        summary: "pairs of nested packets",
+        knobs: day_13::KNOBS,
+        generate: day_13::generate,
+    },
+];
+
+pub fn generator(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+/// `count` lines built by `line`, joined without a final newline.
+pub(crate) fn lines(count: usize, mut line: impl FnMut() -> String) -> String {
+    (0..count).map(|_| line()).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_are_reproducible() {
+        for generator in &GENERATORS {
+            let first = generator.generate(7, &[]).unwrap();
+            assert_eq!(first, generator.generate(7, &[]).unwrap());
+            assert_ne!(first, generator.generate(8, &[]).unwrap());
+        }
+    }
+
+    #[test]
+    fn overrides_knobs() {
+        let generator = generator(8).unwrap();
+        let overrides = [("width".to_string(), 3), ("height".to_string(), 2)];
+        assert_eq!(7, generator.generate(1, &overrides).unwrap().len());
+        assert_eq!(
+            Err(Error::UnknownKnob("depth".to_string())),
+            generator.generate(1, &[("depth".to_string(), 3)])
+        );
+    }
+}
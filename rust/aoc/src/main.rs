@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use aoc_args::Args;
+
+const USAGE: &str = "Usage: aoc gen --day N [--seed SEED] [--KNOB VALUE]...
+
+Prints a random puzzle input for day N, sized by the knobs below. The same
+seed and knobs give the same input from the same build; without --seed a
+random seed is used and printed to standard error.";
+
+struct Options {
+    day: u32,
+    seed: Option<u64>,
+    knobs: Vec<(String, usize)>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = Args::new(args);
+    match args.next() {
+        Some("gen") => {}
+        Some(command) => return Err(format!("unknown command {}", command)),
+        None => return Err("missing command".to_string()),
+    }
+    let mut day = None;
+    let mut options = Options {
+        day: 0,
+        seed: None,
+        knobs: vec![],
+    };
+    while let Some(arg) = args.next() {
+        match arg {
+            "--day" => day = Some(args.parse(arg)?),
+            "--seed" => options.seed = Some(args.parse(arg)?),
+            _ => match arg.strip_prefix("--") {
+                Some(knob) => options.knobs.push((knob.to_string(), args.parse(arg)?)),
+                None => return Err(format!("unexpected argument {}", arg)),
+            },
+        }
+    }
+    options.day = day.ok_or("--day is required")?;
+    Ok(options)
+}
+
+/// The usage, followed by each day's knobs and their defaults.
+fn usage() -> String {
+    let mut usage = format!("{}\n", USAGE);
+    for generator in &aoc::GENERATORS {
+        usage += &format!("\nDay {}: {}\n", generator.day, generator.summary);
+        for knob in generator.knobs {
+            let flag = format!("--{} N", knob.name);
+            usage += &format!("  {:<12} {} (default {})\n", flag, knob.help, knob.default);
+        }
+    }
+    usage
+}
+
+fn main() {
+    let args = aoc_args::from_env();
+    let fail = |err: &dyn Display| -> ! { aoc_args::usage_error(err, &usage()) };
+    let options = parse_args(&args).unwrap_or_else(|err| fail(&err));
+    let generator = aoc::generator(options.day)
+        .unwrap_or_else(|| fail(&format!("no generator for day {}", options.day)));
+    let seed = options.seed.unwrap_or_else(|| {
+        let seed = rand::random();
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    match generator.generate(seed, &options.knobs) {
+        Ok(input) => print!("{}", input),
+        Err(err) => fail(&format!("day {}: {}", options.day, err)),
+    }
+}
@@ -1,6 +1,12 @@
-use std::fs;
+use std::{fs, process};
 
 fn main() {
     let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", day_03::part1(&input));
+    match day_03::part1(&input) {
+        Ok(priorities) => println!("{}", priorities),
+        Err(err) => {
+            eprintln!("input.txt: {}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,101 @@
+use std::fmt;
+use std::ops::BitAnd;
+
+/// The priority of an item: `a` to `z` are 1 to 26 and `A` to `Z` are 27 to
+/// 52. Anything else isn't an item.
+pub fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// A set of items, stored as one bit per priority.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// Collects the items in `items`, failing on the first character that
+    /// isn't a letter.
+    pub fn parse(items: &str) -> Result<Self, char> {
+        let mut set = ItemSet::default();
+        for item in items.chars() {
+            set.0 |= 1 << priority(item).ok_or(item)?;
+        }
+        Ok(set)
+    }
+
+    pub fn contains(self, priority: u32) -> bool {
+        self.0 & 1 << priority != 0
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The priority of the only item, or `None` if there are none or several.
+    pub fn single(self) -> Option<u32> {
+        (self.len() == 1).then(|| self.0.trailing_zeros())
+    }
+
+    /// The priorities of the items, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |&priority| self.contains(priority))
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+/// Lists the items as letters, e.g. `{'a', 'Z'}`.
+impl fmt::Debug for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letter = |priority: u32| match priority {
+            1..=26 => (b'a' + priority as u8 - 1) as char,
+            _ => (b'A' + priority as u8 - 27) as char,
+        };
+        f.debug_set()
+            .entries(self.priorities().map(letter))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_works() {
+        assert_eq!(Some(16), priority('p'));
+        assert_eq!(Some(38), priority('L'));
+        assert_eq!(Some(1), priority('a'));
+        assert_eq!(Some(52), priority('Z'));
+        assert_eq!(None, priority('1'));
+        assert_eq!(None, priority('é'));
+    }
+
+    #[test]
+    fn item_set_works() {
+        let left = ItemSet::parse("vJrwpWtwJgWr").unwrap();
+        let right = ItemSet::parse("hcsFMMfFFhFp").unwrap();
+        assert_eq!(Some(16), (left & right).single());
+        assert_eq!(8, left.len());
+        assert_eq!(None, left.single());
+        assert!(ItemSet::default().is_empty());
+        assert_eq!(Err('-'), ItemSet::parse("ab-c"));
+        assert_eq!(
+            "{'a', 'c', 'Z'}",
+            format!("{:?}", ItemSet::parse("ZcaZ").unwrap())
+        );
+    }
+}
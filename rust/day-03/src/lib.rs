@@ -1,49 +1,136 @@
-#![feature(iter_array_chunks)]
+use std::fmt;
 
-use std::collections::BTreeSet;
+mod items;
+#[cfg(test)]
+mod naive;
 
-fn letter_score(c: char) -> i32 {
-    ('a'..='z')
-        .chain('A'..='Z')
-        .enumerate()
-        .find_map(|(idx, ch)| if c == ch { Some(idx as i32 + 1) } else { None })
-        .unwrap()
+pub use items::{priority, ItemSet};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A rucksack can't be split into two equal compartments.
+    OddLength,
+    /// Something other than a letter.
+    InvalidItem(char),
+    NoCommonItem,
+    /// The last group only has this many rucksacks.
+    IncompleteGroup(usize),
+}
+
+/// Why the input couldn't be scored, with the 1-based line of the rucksack,
+/// or of the first rucksack of the group.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub kind: ErrorKind,
 }
 
-pub fn part1(input: &str) -> i32 {
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ErrorKind::OddLength => write!(f, "odd number of items"),
+            ErrorKind::InvalidItem(item) => write!(f, "{:?} isn't an item", item),
+            ErrorKind::NoCommonItem => write!(f, "no item in common"),
+            ErrorKind::IncompleteGroup(1) => write!(f, "the last group only has 1 rucksack"),
+            ErrorKind::IncompleteGroup(rucksacks) => {
+                write!(f, "the last group only has {} rucksacks", rucksacks)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn item_set(line: usize, items: &str) -> Result<ItemSet, Error> {
+    ItemSet::parse(items).map_err(|item| Error {
+        line,
+        kind: ErrorKind::InvalidItem(item),
+    })
+}
+
+/// The priority of the item in every set. The puzzle promises there's only
+/// one; if there are several this takes the first in character order, so
+/// uppercase before lowercase, as the first solution did.
+fn common_priority(line: usize, sets: &[ItemSet]) -> Result<u32, Error> {
+    let common = sets
+        .iter()
+        .copied()
+        .reduce(|common, set| common & set)
+        .unwrap_or_default();
+    common
+        .priorities()
+        .find(|&priority| priority > 26)
+        .or_else(|| common.priorities().next())
+        .ok_or(Error {
+            line,
+            kind: ErrorKind::NoCommonItem,
+        })
+}
+
+pub fn part1(input: &str) -> Result<u32, Error> {
     input
         .lines()
-        .map(|line| line.split_at(line.len() / 2))
-        .map(|(compartment_1, compartment_2)| {
-            *compartment_1
-                .chars()
-                .collect::<BTreeSet<char>>()
-                .intersection(&compartment_2.chars().collect())
-                .next()
-                .unwrap()
+        .enumerate()
+        .map(|(idx, rucksack)| {
+            let line = idx + 1;
+            // checked before splitting, which could land inside a non-ASCII character
+            item_set(line, rucksack)?;
+            if rucksack.len() % 2 != 0 {
+                return Err(Error {
+                    line,
+                    kind: ErrorKind::OddLength,
+                });
+            }
+            let (compartment_1, compartment_2) = rucksack.split_at(rucksack.len() / 2);
+            common_priority(
+                line,
+                &[
+                    item_set(line, compartment_1)?,
+                    item_set(line, compartment_2)?,
+                ],
+            )
         })
-        .map(|c| letter_score(c))
         .sum()
 }
 
-pub fn part2(input: &str) -> i32 {
-    input
+/// Sums the priorities of the one item carried by every elf in each group of
+/// `group_size` consecutive rucksacks.
+///
+/// # Panics
+///
+/// If `group_size` is 0.
+pub fn badges(input: &str, group_size: usize) -> Result<u32, Error> {
+    assert!(group_size > 0, "groups need at least one elf");
+    let elves = input
         .lines()
-        .map(|elf| elf.chars().collect::<BTreeSet<char>>())
-        .array_chunks::<3>()
-        .map(|chunk| {
-            *(&(&chunk[0] & &chunk[1]) & &chunk[2])
-                .iter()
-                .next()
-                .unwrap()
+        .enumerate()
+        .map(|(idx, elf)| item_set(idx + 1, elf))
+        .collect::<Result<Vec<_>, _>>()?;
+    elves
+        .chunks(group_size)
+        .enumerate()
+        .map(|(idx, group)| {
+            let line = idx * group_size + 1;
+            if group.len() < group_size {
+                return Err(Error {
+                    line,
+                    kind: ErrorKind::IncompleteGroup(group.len()),
+                });
+            }
+            common_priority(line, group)
         })
-        .map(|c| letter_score(c))
         .sum()
 }
 
+pub fn part2(input: &str) -> Result<u32, Error> {
+    badges(input, 3)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_check::{check, Rng};
 
     const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -55,12 +142,101 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
     #[test]
     fn test_part1() {
         let result = part1(INPUT);
-        assert_eq!(result, 157);
+        assert_eq!(result, Ok(157));
     }
 
     #[test]
     fn test_part2() {
         let result = part2(INPUT);
-        assert_eq!(result, 70);
+        assert_eq!(result, Ok(70));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |line, kind| Err(Error { line, kind });
+        assert_eq!(error(2, ErrorKind::OddLength), part1("abca\nabc"));
+        assert_eq!(error(1, ErrorKind::InvalidItem('1')), part1("a1ba"));
+        assert_eq!(error(1, ErrorKind::InvalidItem('é')), part1("aé"));
+        assert_eq!(error(1, ErrorKind::NoCommonItem), part1("abcd"));
+        assert_eq!(Ok(1), part1("abab"));
+        assert_eq!(Ok(27), part1("aAbaAc"));
+        assert_eq!(error(4, ErrorKind::NoCommonItem), part2("a\na\na\na\nb\nc"));
+        assert_eq!(
+            error(4, ErrorKind::IncompleteGroup(2)),
+            part2("a\na\na\nb\nb")
+        );
+        let err = part2("a").unwrap_err();
+        assert_eq!(
+            "line 1: the last group only has 1 rucksack",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn other_group_sizes() {
+        assert_eq!(Ok(2 + 4), badges("ab\nbc\ncd\nde", 2));
+        assert_eq!(Ok(26), badges("zZ\nz\nxz\nyz", 4));
+        assert_eq!(
+            Err(Error {
+                line: 1,
+                kind: ErrorKind::IncompleteGroup(6)
+            }),
+            badges(INPUT, 7)
+        );
+    }
+
+    const LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    /// `count` rucksacks that share at least one item. Every other letter goes
+    /// in the rucksacks picked by the bits of its `owners`, which is sometimes
+    /// all of them.
+    fn sharing(rng: &mut Rng, count: usize) -> Vec<String> {
+        let all = (1u8 << count) - 1;
+        let common = rng.range(0..52);
+        let mut rucksacks = vec![String::new(); count];
+        for (idx, letter) in LETTERS.chars().enumerate() {
+            // about half the time, the common item is the only one
+            let owners = if idx == common || rng.ratio(1, 75) {
+                all
+            } else {
+                rng.range(0..all)
+            };
+            for (bit, rucksack) in rucksacks.iter_mut().enumerate() {
+                if owners & 1 << bit != 0 {
+                    rucksack.push(letter);
+                }
+            }
+        }
+        rucksacks
+    }
+
+    /// A rucksack whose compartments share at least one item.
+    fn rucksack(rng: &mut Rng) -> String {
+        let mut compartments = sharing(rng, 2);
+        // pad the shorter compartment with the items it already has
+        let len = compartments[0].len().max(compartments[1].len());
+        for compartment in &mut compartments {
+            let first = compartment.chars().next().unwrap();
+            while compartment.len() < len {
+                compartment.push(first);
+            }
+        }
+        compartments.concat()
+    }
+
+    #[test]
+    fn part1_matches_naive() {
+        check(|rng| {
+            let input = rng.vec(0..10, rucksack).join("\n");
+            assert_eq!(Ok(naive::part1(&input) as u32), part1(&input));
+        });
+    }
+
+    #[test]
+    fn part2_matches_naive() {
+        check(|rng| {
+            let input = rng.vec(0..5, |rng| sharing(rng, 3)).concat().join("\n");
+            assert_eq!(Ok(naive::part2(&input) as u32), part2(&input));
+        });
     }
 }
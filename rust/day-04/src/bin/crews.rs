@@ -0,0 +1,41 @@
+use std::{fs, process};
+
+use day_04::{Analysis, Interval};
+
+fn list(intervals: &[Interval]) -> String {
+    let list = intervals
+        .iter()
+        .map(Interval::to_string)
+        .collect::<Vec<_>>();
+    if list.is_empty() {
+        "nothing".to_string()
+    } else {
+        list.join(",")
+    }
+}
+
+/// Analyses each line of the input as a crew of any number of elves.
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    let crews = day_04::parse_crews(&input).unwrap_or_else(|err| {
+        eprintln!("input.txt: {}", err);
+        process::exit(1);
+    });
+    for (idx, elves) in crews.iter().enumerate() {
+        let analysis = Analysis::new(elves);
+        let cover = analysis
+            .cover
+            .iter()
+            .map(|idx| (idx + 1).to_string())
+            .collect::<Vec<_>>();
+        println!(
+            "line {}: covers {} ({}), shared {} ({}), needs elves {}",
+            idx + 1,
+            list(&analysis.coverage),
+            analysis.covered_sections(),
+            list(&analysis.shared),
+            analysis.shared_sections(),
+            cover.join(",")
+        );
+    }
+}
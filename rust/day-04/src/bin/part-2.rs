@@ -1,6 +1,12 @@
-use std::fs;
+use std::{fs, process};
 
 fn main() {
     let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", day_04::part2(&input));
+    match day_04::part2(&input) {
+        Ok(count) => println!("{}", count),
+        Err(err) => {
+            eprintln!("input.txt: {}", err);
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,196 @@
+//! Analysing a whole crew's assignments rather than a pair's.
+
+use crate::Interval;
+
+/// What a crew's assignments add up to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    /// The sections anyone is assigned to, as separate intervals in order.
+    pub coverage: Vec<Interval>,
+    /// The sections more than one elf is assigned to, as separate intervals
+    /// in order.
+    pub shared: Vec<Interval>,
+    /// The indices of a smallest set of elves that still covers `coverage`,
+    /// in order.
+    pub cover: Vec<usize>,
+}
+
+impl Analysis {
+    pub fn new(elves: &[Interval]) -> Self {
+        let mut sorted = elves.to_vec();
+        sorted.sort();
+
+        let mut coverage = Vec::new();
+        let mut shared = Vec::new();
+        // the furthest section reached by the intervals so far
+        let mut reach: Option<u32> = None;
+        for interval in &sorted {
+            if let Some(end) = reach {
+                // everything up to `reach` is already covered by an earlier
+                // interval, since they all start no later than this one
+                if let Some(overlap) = Interval::new(interval.start(), end.min(interval.end())) {
+                    push_merged(&mut shared, overlap);
+                }
+            }
+            push_merged(&mut coverage, *interval);
+            reach = reach.max(Some(interval.end()));
+        }
+
+        Analysis {
+            coverage,
+            shared,
+            cover: minimal_cover(elves),
+        }
+    }
+
+    /// How many sections anyone is assigned to.
+    pub fn covered_sections(&self) -> u64 {
+        self.coverage.iter().map(Interval::sections).sum()
+    }
+
+    /// How many sections more than one elf is assigned to.
+    pub fn shared_sections(&self) -> u64 {
+        self.shared.iter().map(Interval::sections).sum()
+    }
+}
+
+/// Adds `interval` to a list sorted by start, merging it into the last one
+/// if they touch.
+fn push_merged(intervals: &mut Vec<Interval>, interval: Interval) {
+    match intervals
+        .last_mut()
+        .and_then(|last| last.union(&interval).map(|union| (last, union)))
+    {
+        Some((last, union)) => *last = union,
+        None => intervals.push(interval),
+    }
+}
+
+/// Greedy interval cover: working left to right, of the intervals that start
+/// by the first uncovered section, keep the one that reaches furthest.
+fn minimal_cover(elves: &[Interval]) -> Vec<usize> {
+    let mut order = (0..elves.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&idx| elves[idx].start());
+    let mut next = order.into_iter().peekable();
+
+    let mut cover = Vec::new();
+    // widened so coverage can run up to u32::MAX
+    let mut uncovered = 0u64;
+    loop {
+        let mut best: Option<usize> = None;
+        while let Some(idx) = next.next_if(|&idx| elves[idx].start() as u64 <= uncovered) {
+            let end = elves[idx].end() as u64;
+            if end >= uncovered && best.is_none_or(|best| end > elves[best].end() as u64) {
+                best = Some(idx);
+            }
+        }
+        match (best, next.peek()) {
+            (Some(idx), _) => {
+                cover.push(idx);
+                uncovered = elves[idx].end() as u64 + 1;
+            }
+            // a gap in the coverage, so skip to where it carries on
+            (None, Some(&idx)) => uncovered = elves[idx].start() as u64,
+            (None, None) => break,
+        }
+    }
+    cover.sort();
+    cover
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_check::{check, Rng};
+    use std::collections::BTreeMap;
+
+    fn interval(start: u32, end: u32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn analysis_works() {
+        let elves = [
+            interval(2, 4),
+            interval(3, 8),
+            interval(4, 5),
+            interval(10, 12),
+            interval(13, 13),
+        ];
+        let analysis = Analysis::new(&elves);
+        assert_eq!(vec![interval(2, 8), interval(10, 13)], analysis.coverage);
+        assert_eq!(vec![interval(3, 5)], analysis.shared);
+        assert_eq!(vec![0, 1, 3, 4], analysis.cover);
+        assert_eq!(
+            (11, 3),
+            (analysis.covered_sections(), analysis.shared_sections())
+        );
+    }
+
+    #[test]
+    fn empty_crew() {
+        let analysis = Analysis::new(&[]);
+        assert!(
+            analysis.coverage.is_empty() && analysis.shared.is_empty() && analysis.cover.is_empty()
+        );
+    }
+
+    fn crew(rng: &mut Rng) -> Vec<Interval> {
+        rng.vec(0..8, |rng| {
+            let start = rng.range(0..40);
+            interval(start, start + rng.range(0..10))
+        })
+    }
+
+    /// How many elves are assigned to each section.
+    fn counts(elves: &[Interval]) -> BTreeMap<u32, usize> {
+        let mut counts = BTreeMap::new();
+        for interval in elves {
+            for section in interval.start()..=interval.end() {
+                *counts.entry(section).or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    fn sections(intervals: &[Interval]) -> Vec<u32> {
+        intervals.iter().flat_map(|i| i.start()..=i.end()).collect()
+    }
+
+    fn covered(elves: &[Interval]) -> Vec<u32> {
+        counts(elves).into_keys().collect()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        check(|rng| {
+            let elves = crew(rng);
+            let analysis = Analysis::new(&elves);
+            let shared = counts(&elves)
+                .into_iter()
+                .filter(|&(_, count)| count > 1)
+                .map(|(section, _)| section)
+                .collect::<Vec<_>>();
+            assert_eq!(covered(&elves), sections(&analysis.coverage));
+            assert_eq!(shared, sections(&analysis.shared));
+
+            let chosen = analysis
+                .cover
+                .iter()
+                .map(|&idx| elves[idx])
+                .collect::<Vec<_>>();
+            assert_eq!(covered(&elves), covered(&chosen));
+            // no smaller set of elves covers everything
+            for mask in 0u32..1 << elves.len() {
+                if mask.count_ones() as usize >= chosen.len() {
+                    continue;
+                }
+                let subset = (0..elves.len())
+                    .filter(|idx| mask & 1 << idx != 0)
+                    .map(|idx| elves[idx])
+                    .collect::<Vec<_>>();
+                assert_ne!(covered(&elves), covered(&subset));
+            }
+        });
+    }
+}
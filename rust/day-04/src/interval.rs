@@ -0,0 +1,162 @@
+use std::fmt;
+
+/// The sections `start..=end`. Never empty: a reversed range isn't an
+/// `Interval`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    start: u32,
+    end: u32,
+}
+
+impl Interval {
+    /// `None` if `end` comes before `start`.
+    pub fn new(start: u32, end: u32) -> Option<Self> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// How many sections the interval covers.
+    pub fn sections(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+
+    pub fn contains_section(&self, section: u32) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Whether every section of `other` is also in `self`.
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether any section is in both.
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// The sections in both, if there are any.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The sections in either, if they form a single interval; that is, if
+    /// the two overlap or one ends right before the other starts.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        // widened so an interval ending at u32::MAX doesn't overflow
+        if second.start as u64 > first.end as u64 + 1 {
+            return None;
+        }
+        Some(Interval {
+            start: first.start,
+            end: first.end.max(second.end),
+        })
+    }
+}
+
+/// Prints the interval the way the puzzle input writes it, e.g. `2-4`.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_check::{check, Rng};
+    use std::collections::BTreeSet;
+    use std::ops::RangeInclusive;
+
+    fn interval(start: u32, end: u32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_reversed() {
+        assert_eq!(None, Interval::new(8, 2));
+        assert_eq!(1, interval(6, 6).sections());
+    }
+
+    #[test]
+    fn interval_works() {
+        let a = interval(2, 8);
+        let b = interval(3, 7);
+        assert!(a.contains(&b) && !b.contains(&a));
+        assert!(a.overlaps(&b));
+        assert_eq!(Some(b), a.intersection(&b));
+        assert_eq!(Some(a), a.union(&b));
+
+        let c = interval(9, 12);
+        assert!(!a.overlaps(&c));
+        assert_eq!(None, a.intersection(&c));
+        assert_eq!(Some(interval(2, 12)), a.union(&c));
+        assert_eq!(None, a.union(&interval(10, 12)));
+        assert_eq!("2-8", a.to_string());
+    }
+
+    #[test]
+    fn union_at_the_end_of_u32() {
+        let end = interval(u32::MAX - 1, u32::MAX);
+        assert_eq!(
+            Some(interval(5, u32::MAX)),
+            interval(5, u32::MAX - 2).union(&end)
+        );
+        assert_eq!(None, interval(5, u32::MAX - 3).union(&end));
+        assert_eq!(Some(end), end.union(&end));
+    }
+
+    fn sections(range: RangeInclusive<u32>) -> BTreeSet<u32> {
+        range.collect()
+    }
+
+    fn random_interval(rng: &mut Rng) -> Interval {
+        let start = rng.range(0..60);
+        interval(start, start + rng.range(0..20))
+    }
+
+    // the brute-force checks day-04 used before intervals
+    #[test]
+    fn matches_brute_force() {
+        check(|rng| {
+            let (a, b) = (random_interval(rng), random_interval(rng));
+            let range_a = a.start()..=a.end();
+            let range_b = b.start()..=b.end();
+            assert_eq!(
+                range_b.clone().all(|section| range_a.contains(&section)),
+                a.contains(&b)
+            );
+            assert_eq!(
+                range_a.clone().any(|section| range_b.contains(&section)),
+                a.overlaps(&b)
+            );
+
+            let both = sections(range_a.clone())
+                .intersection(&sections(range_b.clone()))
+                .copied()
+                .collect::<BTreeSet<_>>();
+            let intersection = a.intersection(&b).map(|i| sections(i.start()..=i.end()));
+            assert_eq!((!both.is_empty()).then_some(both), intersection);
+
+            let either = sections(range_a)
+                .union(&sections(range_b))
+                .copied()
+                .collect::<BTreeSet<_>>();
+            let contiguous =
+                either.len() as u32 == either.last().unwrap() - either.first().unwrap() + 1;
+            let union = a.union(&b).map(|i| sections(i.start()..=i.end()));
+            assert_eq!(contiguous.then_some(either), union);
+        });
+    }
+}
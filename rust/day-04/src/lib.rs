@@ -1,16 +1,31 @@
-use std::ops::RangeInclusive;
-
 use nom::{
-    bytes::complete::tag, character::complete, multi::separated_list0, sequence::separated_pair,
+    bytes::complete::tag,
+    character::complete,
+    combinator::map_opt,
+    multi::{separated_list0, separated_list1},
+    sequence::separated_pair,
     IResult,
 };
+use std::fmt;
+
+mod crew;
+mod interval;
+#[cfg(test)]
+mod naive;
+
+pub use crew::Analysis;
+pub use interval::Interval;
 
 #[derive(Debug, PartialEq)]
-struct RangePair(RangeInclusive<u32>, RangeInclusive<u32>);
+struct RangePair(Interval, Interval);
 
-fn range(input: &str) -> IResult<&str, RangeInclusive<u32>> {
-    let (input, (start, end)) = separated_pair(complete::u32, tag("-"), complete::u32)(input)?;
-    Ok((input, start..=end))
+/// `start-end`, without checking the order.
+fn raw_range(input: &str) -> IResult<&str, (u32, u32)> {
+    separated_pair(complete::u32, tag("-"), complete::u32)(input)
+}
+
+fn range(input: &str) -> IResult<&str, Interval> {
+    map_opt(raw_range, |(start, end)| Interval::new(start, end))(input)
 }
 
 fn range_pair(input: &str) -> IResult<&str, RangePair> {
@@ -18,36 +33,106 @@ fn range_pair(input: &str) -> IResult<&str, RangePair> {
     Ok((input, RangePair(range_a, range_b)))
 }
 
-fn section_assignments(input: &str) -> IResult<&str, Vec<RangePair>> {
-    let (input, assignments) = separated_list0(complete::newline, range_pair)(input)?;
-    Ok((input, assignments))
+/// Any number of comma-separated ranges, one per elf.
+fn crew(input: &str) -> IResult<&str, Vec<Interval>> {
+    separated_list1(tag(","), range)(input)
+}
+
+/// One assignment per line, each parsed by `line`.
+fn section_assignments<'a, O>(
+    line: fn(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list0(complete::newline, line)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The line isn't two ranges like `2-4,6-8`.
+    Malformed,
+    /// The line isn't a crew's ranges like `2-4,6-8,1-3`.
+    MalformedCrew,
+    /// A range on the line ends before it starts, like `8-2`.
+    Reversed,
+}
+
+/// Why the input couldn't be parsed, with the 1-based line it went wrong on.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub kind: ErrorKind,
 }
 
-pub fn part1(input: &str) -> usize {
-    let (_, assignments) = section_assignments(input).unwrap();
-    assignments
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Malformed => {
+                write!(f, "line {}: expected two ranges like 2-4,6-8", self.line)
+            }
+            ErrorKind::MalformedCrew => {
+                write!(f, "line {}: expected ranges like 2-4,6-8,1-3", self.line)
+            }
+            ErrorKind::Reversed => write!(f, "line {}: range ends before it starts", self.line),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses every line with `parse_line`, reporting lines it can't parse as
+/// `malformed` unless they hold a reversed range.
+fn parse_assignments<O>(
+    input: &str,
+    parse_line: fn(&str) -> IResult<&str, O>,
+    malformed: ErrorKind,
+) -> Result<Vec<O>, ParseError> {
+    // separated_list0 stops at the first line it can't parse instead of failing
+    let (rest, assignments) = section_assignments(parse_line)(input).unwrap();
+    if rest.trim().is_empty() {
+        return Ok(assignments);
+    }
+    // the newline before the line it stopped at is left unparsed too
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+    let line = input[..input.len() - rest.len()].matches('\n').count() + 1;
+    // a reversed range makes the line fail to parse, so look for one
+    let bad_line = rest.lines().next().unwrap_or_default();
+    let reversed = bad_line
+        .split(',')
+        .any(|part| matches!(raw_range(part), Ok(("", (start, end))) if start > end));
+    let kind = if reversed {
+        ErrorKind::Reversed
+    } else {
+        malformed
+    };
+    Err(ParseError { line, kind })
+}
+
+/// Parses one crew per line, each with any number of elves.
+pub fn parse_crews(input: &str) -> Result<Vec<Vec<Interval>>, ParseError> {
+    parse_assignments(input, crew, ErrorKind::MalformedCrew)
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let assignments = parse_assignments(input, range_pair, ErrorKind::Malformed)?;
+    Ok(assignments
         .iter()
         .filter(|RangePair(range_a, range_b)| {
-            range_a.clone().into_iter().all(|a| range_b.contains(&a))
-                || range_b.clone().into_iter().all(|b| range_a.contains(&b))
+            range_a.contains(range_b) || range_b.contains(range_a)
         })
-        .count()
+        .count())
 }
 
-pub fn part2(input: &str) -> usize {
-    let (_, assignments) = section_assignments(input).unwrap();
-    assignments
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    let assignments = parse_assignments(input, range_pair, ErrorKind::Malformed)?;
+    Ok(assignments
         .iter()
-        .filter(|RangePair(range_a, range_b)| {
-            range_a.clone().into_iter().any(|a| range_b.contains(&a))
-                || range_b.clone().into_iter().any(|b| range_a.contains(&b))
-        })
-        .count()
+        .filter(|RangePair(range_a, range_b)| range_a.overlaps(range_b))
+        .count())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_check::{check, Rng};
 
     const INPUT: &str = "2-4,6-8
 2-3,4-5
@@ -58,23 +143,91 @@ mod tests {
 
     #[test]
     fn test_parse_range() {
-        assert_eq!(Ok(("", 3..=10)), range("3-10"));
+        assert_eq!(Ok(("", Interval::new(3, 10).unwrap())), range("3-10"));
+        assert!(range("8-2").is_err());
     }
 
     #[test]
     fn test_parse_range_pair() {
-        assert_eq!(Ok(("", RangePair(1..=10, 4..=8))), range_pair("1-10,4-8"))
+        let interval = |start, end| Interval::new(start, end).unwrap();
+        assert_eq!(
+            Ok(("", RangePair(interval(1, 10), interval(4, 8)))),
+            range_pair("1-10,4-8")
+        )
     }
 
     #[test]
     fn test_part1() {
         let result = part1(INPUT);
-        assert_eq!(result, 2);
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part2() {
         let result = part2(INPUT);
-        assert_eq!(result, 4);
+        assert_eq!(result, Ok(4));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let input = INPUT.replace("5-7,7-9", "5-7,9-7");
+        assert_eq!(
+            Err(ParseError {
+                line: 3,
+                kind: ErrorKind::Reversed
+            }),
+            part1(&input)
+        );
+        let input = INPUT.replace("6-6,4-6", "6-6;4-6");
+        let err = part2(&input).unwrap_err();
+        assert_eq!("line 5: expected two ranges like 2-4,6-8", err.to_string());
+        assert_eq!(Ok(4), part2(&format!("{}\n", INPUT)));
+        assert_eq!(
+            Err(ParseError {
+                line: 2,
+                kind: ErrorKind::Malformed
+            }),
+            part1("2-4,6-8\n\n3-4,5-6")
+        );
+    }
+
+    #[test]
+    fn parse_crews_works() {
+        let crews = parse_crews("2-4,6-8,1-3\n5-5\n").unwrap();
+        assert_eq!(vec![3, 1], crews.iter().map(Vec::len).collect::<Vec<_>>());
+        assert_eq!(Interval::new(1, 3), Some(crews[0][2]));
+
+        assert_eq!(
+            Err(ParseError {
+                line: 2,
+                kind: ErrorKind::Reversed
+            }),
+            parse_crews("2-4,6-8,1-3\n5-5,4-3")
+        );
+        let err = parse_crews("2-4,6-8\n5-5;4-3").unwrap_err();
+        assert_eq!("line 2: expected ranges like 2-4,6-8,1-3", err.to_string());
+        // pairs still need exactly two ranges
+        assert!(part1("2-4,6-8,1-3").is_err());
+    }
+
+    /// Lines of two ranges like `2-4,6-8`, never reversed.
+    fn assignments(rng: &mut Rng) -> String {
+        let lines = rng.vec(0..20, |rng| {
+            let mut range = || {
+                let start = rng.range(1..60u32);
+                format!("{}-{}", start, start + rng.range(0..20))
+            };
+            format!("{},{}", range(), range())
+        });
+        lines.join("\n")
+    }
+
+    #[test]
+    fn matches_naive() {
+        check(|rng| {
+            let input = assignments(rng);
+            assert_eq!(Ok(naive::part1(&input)), part1(&input));
+            assert_eq!(Ok(naive::part2(&input)), part2(&input));
+        });
     }
 }
@@ -0,0 +1,48 @@
+//! Range checks that walk every section, slow for wide ranges.
+
+use std::ops::RangeInclusive;
+
+use nom::{
+    bytes::complete::tag, character::complete, multi::separated_list0, sequence::separated_pair,
+    IResult,
+};
+
+#[derive(Debug, PartialEq)]
+struct RangePair(RangeInclusive<u32>, RangeInclusive<u32>);
+
+fn range(input: &str) -> IResult<&str, RangeInclusive<u32>> {
+    let (input, (start, end)) = separated_pair(complete::u32, tag("-"), complete::u32)(input)?;
+    Ok((input, start..=end))
+}
+
+fn range_pair(input: &str) -> IResult<&str, RangePair> {
+    let (input, (range_a, range_b)) = separated_pair(range, tag(","), range)(input)?;
+    Ok((input, RangePair(range_a, range_b)))
+}
+
+fn section_assignments(input: &str) -> IResult<&str, Vec<RangePair>> {
+    let (input, assignments) = separated_list0(complete::newline, range_pair)(input)?;
+    Ok((input, assignments))
+}
+
+pub fn part1(input: &str) -> usize {
+    let (_, assignments) = section_assignments(input).unwrap();
+    assignments
+        .iter()
+        .filter(|RangePair(range_a, range_b)| {
+            range_a.clone().all(|a| range_b.contains(&a))
+                || range_b.clone().all(|b| range_a.contains(&b))
+        })
+        .count()
+}
+
+pub fn part2(input: &str) -> usize {
+    let (_, assignments) = section_assignments(input).unwrap();
+    assignments
+        .iter()
+        .filter(|RangePair(range_a, range_b)| {
+            range_a.clone().any(|a| range_b.contains(&a))
+                || range_b.clone().any(|b| range_a.contains(&b))
+        })
+        .count()
+}
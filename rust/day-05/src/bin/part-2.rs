@@ -1,6 +1,21 @@
-use std::fs;
+use std::{env, fs, process};
+
+use day_05::CrateMover9001;
 
 fn main() {
+    // --render prints the stacks before the first move and after every move
+    let render = env::args().skip(1).any(|arg| arg == "--render");
     let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", day_05::part2(&input));
+    let result = day_05::rearrange_with(&input, &CrateMover9001, " ", |mov, stacks| {
+        if render {
+            print!("{}", day_05::frame(mov, stacks));
+        }
+    });
+    match result {
+        Ok(tops) => println!("{}", tops),
+        Err(err) => {
+            eprintln!("input.txt: {}", err);
+            process::exit(1);
+        }
+    }
 }
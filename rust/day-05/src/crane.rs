@@ -0,0 +1,80 @@
+//! The cranes that rearrange the stacks. [`Stacks::apply`] checks a move is
+//! possible before handing it to a crane, so cranes can assume `from` holds
+//! at least `amount` crates.
+//!
+//! [`Stacks::apply`]: crate::Stacks::apply
+
+use crate::Crate;
+
+pub trait Crane {
+    /// Moves the top `amount` crates of `from` onto `to`.
+    fn transfer(&self, from: &mut Vec<Crate>, to: &mut Vec<Crate>, amount: usize);
+}
+
+/// Lifts one crate at a time, so moved crates end up in reverse order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn transfer(&self, from: &mut Vec<Crate>, to: &mut Vec<Crate>, amount: usize) {
+        let start = from.len() - amount;
+        to.extend(from.drain(start..).rev());
+    }
+}
+
+/// Lifts every crate in one go, so moved crates keep their order.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn transfer(&self, from: &mut Vec<Crate>, to: &mut Vec<Crate>, amount: usize) {
+        let start = from.len() - amount;
+        to.extend(from.drain(start..));
+    }
+}
+
+/// Lifts up to `capacity` crates at a time, keeping the order within each
+/// batch. A capacity of 1 behaves like the [`CrateMover9000`], and one at least
+/// as large as every move like the [`CrateMover9001`].
+#[derive(Clone, Copy, Debug)]
+pub struct BatchCrane {
+    pub capacity: usize,
+}
+
+impl Crane for BatchCrane {
+    fn transfer(&self, from: &mut Vec<Crate>, to: &mut Vec<Crate>, amount: usize) {
+        assert!(self.capacity > 0, "a crane has to lift something");
+        let mut remaining = amount;
+        while remaining > 0 {
+            let batch = remaining.min(self.capacity);
+            CrateMover9001.transfer(from, to, batch);
+            remaining -= batch;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crates(names: &str) -> Vec<Crate> {
+        names.chars().map(|name| Crate(name.to_string())).collect()
+    }
+
+    fn transfer(crane: impl Crane, amount: usize) -> Vec<Crate> {
+        let mut from = crates("ABCDE");
+        let mut to = crates("Z");
+        crane.transfer(&mut from, &mut to, amount);
+        assert_eq!(5 - amount, from.len());
+        to
+    }
+
+    #[test]
+    fn cranes_work() {
+        assert_eq!(crates("ZEDC"), transfer(CrateMover9000, 3));
+        assert_eq!(crates("ZCDE"), transfer(CrateMover9001, 3));
+        assert_eq!(crates("ZDEBC"), transfer(BatchCrane { capacity: 2 }, 4));
+        assert_eq!(crates("ZEDC"), transfer(BatchCrane { capacity: 1 }, 3));
+        assert_eq!(crates("ZCDE"), transfer(BatchCrane { capacity: 9 }, 3));
+    }
+}
@@ -1,62 +1,184 @@
 use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_until},
-    character::complete::{anychar, multispace1, newline, u32 as u32_},
+    bytes::complete::{tag, take_while1},
+    character::complete::{line_ending, multispace1, newline, space0, u32 as u32_},
+    combinator::{map, verify},
     multi::{many1, separated_list0},
     sequence::delimited,
     IResult,
 };
+use std::fmt;
+use std::ops::Range;
 
-#[derive(Debug, PartialEq)]
-struct Crate(char);
+mod crane;
+mod stacks;
 
-fn krate(input: &str) -> IResult<&str, Option<Crate>> {
-    let (input, krate) = delimited(tag("["), anychar, tag("]"))(input)?;
-    Ok((input, Some(Crate(krate))))
+pub use crane::{BatchCrane, Crane, CrateMover9000, CrateMover9001};
+pub use stacks::{ErrorKind, MoveError, Stacks};
+
+/// A crate and its name, which is usually one letter but can be longer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Crate(pub String);
+
+/// Prints the crate the way the drawing does, e.g. `[A]`.
+impl fmt::Display for Crate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0)
+    }
 }
 
-fn empty_slot(input: &str) -> IResult<&str, Option<Crate>> {
-    let (input, _) = tag("   ")(input)?;
-    Ok((input, None))
+/// The rows of the drawing, top row first, with `None` for empty slots.
+type Rows = Vec<Vec<Option<Crate>>>;
+
+/// A crate in a row of the drawing, with the columns its brackets cover.
+type Placed = (Range<usize>, Crate);
+
+fn krate(input: &str) -> IResult<&str, Crate> {
+    let name = take_while1(|c: char| c != '[' && c != ']' && !c.is_whitespace());
+    let (input, name) = delimited(tag("["), name, tag("]"))(input)?;
+    Ok((input, Crate(name.to_string())))
 }
 
-fn crate_slot(input: &str) -> IResult<&str, Option<Crate>> {
-    let (input, krate) = alt((krate, empty_slot))(input)?;
-    Ok((input, krate))
+/// Repeatedly parses `item` separated by spaces until the end of the line,
+/// noting the columns each one covers. Doesn't consume the line ending.
+fn spaced<'a, O>(
+    line: &'a str,
+    mut item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> IResult<&'a str, Vec<(Range<usize>, O)>> {
+    let column = |rest: &str| line.len() - rest.len();
+    let mut items = Vec::new();
+    let mut input = line;
+    loop {
+        let (rest, _) = space0(input)?;
+        if rest.is_empty() || rest.starts_with(['\n', '\r']) {
+            return Ok((rest, items));
+        }
+        let (after, value) = item(rest)?;
+        items.push((column(rest)..column(after), value));
+        input = after;
+    }
 }
 
-fn crate_row(input: &str) -> IResult<&str, Vec<Option<Crate>>> {
-    let (input, crates) = separated_list0(tag(" "), crate_slot)(input)?;
-    Ok((input, crates))
+/// A row of crates. Empty slots are just spaces, and trailing ones may have
+/// been trimmed. There's always at least one crate, or it's not a crate row.
+fn crate_row(input: &str) -> IResult<&str, Vec<Placed>> {
+    let (rest, crates) = spaced(input, krate)?;
+    let (rest, _) = line_ending(rest)?;
+    if crates.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Many1,
+        )));
+    }
+    Ok((rest, crates))
 }
 
-fn crate_stacks(input: &str) -> IResult<&str, Vec<Vec<Option<Crate>>>> {
-    let (input, crate_stacks) = separated_list0(newline, crate_row)(input)?;
-    Ok((input, crate_stacks))
+/// The stack labels under the drawing, which must count up from 1, with the
+/// columns each covers.
+fn numbers_row(input: &str) -> IResult<&str, Vec<Range<usize>>> {
+    let (rest, labels) = verify(
+        |input| spaced(input, u32_),
+        |labels: &Vec<(Range<usize>, u32)>| {
+            !labels.is_empty()
+                && labels
+                    .iter()
+                    .map(|(_, label)| *label)
+                    .eq(1..=labels.len() as u32)
+        },
+    )(input)?;
+    Ok((
+        rest,
+        labels.into_iter().map(|(columns, _)| columns).collect(),
+    ))
 }
 
-#[derive(Debug, PartialEq)]
-struct Move {
-    amount: u32,
-    from: u32,
-    to: u32,
+/// Puts each crate in the slot of the one label its brackets overlap, with
+/// the labels moved `shift` columns left. Fails with the column of a crate
+/// that isn't above exactly one label, or shares a slot with another crate.
+fn align(
+    row: &[Placed],
+    labels: &[Range<usize>],
+    shift: usize,
+) -> Result<Vec<Option<Crate>>, usize> {
+    let mut slots = vec![None; labels.len()];
+    for (columns, krate) in row {
+        let mut under = labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| {
+                label.start - shift < columns.end && columns.start < label.end - shift
+            })
+            .map(|(idx, _)| idx);
+        match (under.next(), under.next()) {
+            (Some(idx), None) if slots[idx].is_none() => slots[idx] = Some(krate.clone()),
+            _ => return Err(columns.start),
+        }
+    }
+    Ok(slots)
+}
+
+/// The whole drawing, up to the end of the numbers row. Stack positions come
+/// from the numbers row, so crates only need to sit above their label.
+///
+/// If they don't, the numbers row may just be indented: it's moved left a
+/// column at a time until they do, but never past the one space the puzzle
+/// puts before `1`, so a crate between two labels is still an error.
+fn drawing(input: &str) -> IResult<&str, Stacks> {
+    let mut placed = Vec::new();
+    let mut rest = input;
+    while let Ok((after, row)) = crate_row(rest) {
+        placed.push((rest, row));
+        rest = after;
+    }
+    let (rest, labels) = numbers_row(rest)?;
+    let align_all = |shift| {
+        placed
+            .iter()
+            .map(|(line, row)| align(row, &labels, shift).map_err(|column| &line[column..]))
+            .collect::<Result<Rows, _>>()
+    };
+    let rows = (0..labels[0].start.max(1))
+        .find_map(|shift| align_all(shift).ok())
+        .map_or_else(|| align_all(0), Ok)
+        .map_err(|line| {
+            nom::Err::Failure(nom::error::Error::new(line, nom::error::ErrorKind::Verify))
+        })?;
+    Ok((rest, Stacks::from_rows(labels.len(), &rows)))
+}
+
+/// One line of the rearrangement procedure, with 0-based stack indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub amount: u32,
+    pub from: u32,
+    pub to: u32,
+}
+
+/// Prints the move as it appears in the input, with 1-based stack numbers.
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.amount,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/// A 1-based stack number, returned 0-based.
+fn stack_number(input: &str) -> IResult<&str, u32> {
+    map(verify(u32_, |&number| number > 0), |number| number - 1)(input)
 }
 
 fn mov(input: &str) -> IResult<&str, Move> {
     let (input, _) = tag("move ")(input)?;
     let (input, amount) = u32_(input)?;
     let (input, _) = tag(" from ")(input)?;
-    let (input, from) = u32_(input)?;
+    let (input, from) = stack_number(input)?;
     let (input, _) = tag(" to ")(input)?;
-    let (input, to) = u32_(input)?;
-    Ok((
-        input,
-        Move {
-            amount,
-            from: from - 1,
-            to: to - 1,
-        },
-    ))
+    let (input, to) = stack_number(input)?;
+    Ok((input, Move { amount, from, to }))
 }
 
 fn moves(input: &str) -> IResult<&str, Vec<Move>> {
@@ -64,92 +186,120 @@ fn moves(input: &str) -> IResult<&str, Vec<Move>> {
     Ok((input, moves))
 }
 
-fn numbers_row(input: &str) -> IResult<&str, ()> {
-    let (input, _) = take_until("\n")(input)?;
-    Ok((input, ()))
+struct Procedure {
+    stacks: Stacks,
+    /// The 1-based line number of the first move.
+    first_move_line: usize,
+    moves: Vec<Move>,
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<Vec<Option<Crate>>>, Vec<Move>)> {
-    let (input, stacks) = crate_stacks(input)?;
-    // eat the numbers row
-    let (input, _) = numbers_row(input)?;
+fn parse_input(input: &str) -> IResult<&str, Procedure> {
+    let full_input = input;
+    let (input, stacks) = drawing(input)?;
     // eat the blank line
     let (input, _) = many1(multispace1)(input)?;
+    let first_move_line = line_number(full_input, input);
     let (input, moves) = moves(input)?;
-    Ok((input, (stacks, moves)))
+    Ok((
+        input,
+        Procedure {
+            stacks,
+            first_move_line,
+            moves,
+        },
+    ))
 }
 
-pub fn part1(input: &str) -> String {
-    let (_, (stacks_horiz, moves)) = parse_input(input).unwrap();
+/// The 1-based line of `input` that `rest` starts on.
+fn line_number(input: &str, rest: &str) -> usize {
+    input[..input.len() - rest.len()].matches('\n').count() + 1
+}
 
-    let mut stacks: Vec<Vec<&Crate>> = vec![];
-    for _ in 0..stacks_horiz[0].len() {
-        stacks.push(vec![]);
-    }
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input stops making sense on this 1-based line.
+    Parse { line: usize },
+    /// The move on this 1-based line can't be carried out.
+    Move { line: usize, error: MoveError },
+}
 
-    for row in stacks_horiz.iter().rev() {
-        for (i, maybe_crate) in row.iter().enumerate() {
-            if let Some(krate) = maybe_crate {
-                stacks[i].push(krate);
-            }
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse { line } => write!(f, "line {}: can't parse this line", line),
+            Error::Move { line, error } => write!(f, "line {}: {}", line, error),
         }
     }
+}
 
-    for mov in moves.iter() {
-        for _ in 0..mov.amount {
-            let krate = stacks[mov.from as usize].pop().unwrap();
-            stacks[mov.to as usize].push(krate);
-        }
-    }
+impl std::error::Error for Error {}
 
-    stacks
-        .iter()
-        .map(|stack| stack.last().unwrap())
-        .map(|Crate(char)| char)
-        .collect()
+/// Runs the whole procedure with `crane` and returns the top crates, with
+/// `placeholder` for any empty stack.
+pub fn rearrange(input: &str, crane: &impl Crane, placeholder: &str) -> Result<String, Error> {
+    rearrange_with(input, crane, placeholder, |_, _| {})
 }
 
-pub fn part2(input: &str) -> String {
-    let (_, (stacks_horiz, moves)) = parse_input(input).unwrap();
-
-    let mut stacks: Vec<Vec<&Crate>> = vec![];
-    for _ in 0..stacks_horiz[0].len() {
-        stacks.push(vec![]);
+/// Like [`rearrange`], but calls `inspect` with the starting stacks and then
+/// after every move, e.g. to print each step.
+pub fn rearrange_with<F>(
+    input: &str,
+    crane: &impl Crane,
+    placeholder: &str,
+    mut inspect: F,
+) -> Result<String, Error>
+where
+    F: FnMut(Option<&Move>, &Stacks),
+{
+    let parse_error = |rest: &str| Error::Parse {
+        line: line_number(input, rest.trim_start()),
+    };
+    let (rest, procedure) = parse_input(input).map_err(|err| match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => parse_error(err.input),
+        nom::Err::Incomplete(_) => parse_error(""),
+    })?;
+    if !rest.trim().is_empty() {
+        return Err(parse_error(rest));
     }
 
-    for row in stacks_horiz.iter().rev() {
-        for (i, maybe_crate) in row.iter().enumerate() {
-            if let Some(krate) = maybe_crate {
-                stacks[i].push(krate);
-            }
-        }
+    let mut stacks = procedure.stacks;
+    inspect(None, &stacks);
+    for (idx, mov) in procedure.moves.iter().enumerate() {
+        stacks.apply(mov, crane).map_err(|error| Error::Move {
+            line: procedure.first_move_line + idx,
+            error,
+        })?;
+        inspect(Some(mov), &stacks);
     }
+    Ok(stacks.tops_or(placeholder))
+}
 
-    for Move { amount, from, to } in moves.iter() {
-        let len = stacks[*from as usize].len();
-        let crates = stacks[*from as usize]
-            .drain((len - *amount as usize)..)
-            .collect::<Vec<&Crate>>();
-        for krate in crates {
-            stacks[*to as usize].push(krate);
-        }
+/// One step of the procedure as text for [`rearrange_with`]'s `inspect`:
+/// the move, if there was one, then the stacks and a blank line.
+pub fn frame(mov: Option<&Move>, stacks: &Stacks) -> String {
+    match mov {
+        Some(mov) => format!("{}\n{}\n\n", mov, stacks),
+        None => format!("{}\n\n", stacks),
     }
+}
+
+pub fn part1(input: &str) -> Result<String, Error> {
+    rearrange(input, &CrateMover9000, " ")
+}
 
-    stacks
-        .iter()
-        .map(|stack| stack.last().unwrap())
-        .map(|Crate(char)| char)
-        .collect()
+pub fn part2(input: &str) -> Result<String, Error> {
+    rearrange(input, &CrateMover9001, " ")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_check::{check, Rng};
 
     const INPUT: &str = "    [D]    
 [N] [C]    
 [Z] [M] [P]
-    1   2   3 
+ 1   2   3 
 
 move 1 from 2 to 1
 move 3 from 1 to 3
@@ -159,51 +309,99 @@ move 1 from 1 to 2";
     #[test]
     fn part1_works() {
         let result = part1(INPUT);
-        assert_eq!(result, "CMZ".to_string());
+        assert_eq!(result, Ok("CMZ".to_string()));
     }
 
     #[test]
     fn part2_works() {
         let result = part2(INPUT);
-        assert_eq!(result, "MCD".to_string());
+        assert_eq!(result, Ok("MCD".to_string()));
+    }
+
+    fn krate(name: &str) -> Crate {
+        Crate(name.to_string())
     }
 
     #[test]
     fn krate_works() {
-        assert_eq!(Ok(("", Some(Crate('A')))), krate("[A]"))
+        assert_eq!(Ok(("", krate("A"))), super::krate("[A]"));
+        assert_eq!(Ok((" ", krate("AB1"))), super::krate("[AB1] "));
+        assert!(super::krate("[]").is_err());
+        assert!(super::krate("[A B]").is_err());
+    }
+
+    #[test]
+    fn crate_row_works() {
+        assert_eq!(
+            Ok(("", vec![(0..3, krate("A")), (8..11, krate("C"))])),
+            crate_row("[A]     [C]\n")
+        );
+        assert_eq!(
+            Ok(("x", vec![(4..8, krate("ZZ"))])),
+            crate_row("    [ZZ]  \r\nx")
+        );
+        // the numbers row has no crates
+        assert!(crate_row(" 1   2 \n").is_err());
     }
 
     #[test]
-    fn empty_slot_works() {
-        assert_eq!(Ok(("", None)), empty_slot("   "))
+    fn drawing_works() {
+        let (rest, stacks) = drawing("    [Z] [V]\n[A] [B]\n 1   2   3").unwrap();
+        assert_eq!("", rest);
+        assert_eq!(
+            &[
+                vec![krate("A")],
+                vec![krate("B"), krate("Z")],
+                vec![krate("V")]
+            ],
+            stacks.stacks()
+        );
     }
 
     #[test]
-    fn crate_slot_works() {
-        assert_eq!(Ok(("", Some(Crate('M')))), crate_slot("[M]"));
-        assert_eq!(Ok(("", None)), crate_slot("   "));
+    fn drawing_tolerates_trimmed_lines() {
+        // every line has lost its trailing spaces, including the first
+        let (_, stacks) = drawing("    [D]\n[N] [C]\n 1   2   3\n").unwrap();
+        assert_eq!(3, stacks.stacks().len());
+        assert!(stacks.stacks()[2].is_empty());
     }
 
     #[test]
-    fn crate_row_works() {
+    fn drawing_with_wide_crates() {
+        let text = "     [Cab]\n[Aa] [Bbb]\n 1     2  ";
+        let (_, stacks) = drawing(text).unwrap();
         assert_eq!(
-            Ok(("", vec![Some(Crate('A')), None, Some(Crate('C'))])),
-            crate_row("[A]     [C]")
-        )
+            &[vec![krate("Aa")], vec![krate("Bbb"), krate("Cab")]],
+            stacks.stacks()
+        );
+        assert_eq!(text, stacks.to_string());
     }
 
     #[test]
-    fn crate_stacks_works() {
+    fn drawing_ignores_numbers_row_indent() {
+        let (_, stacks) = drawing("[A]     [C]\n1   2   3\n").unwrap();
         assert_eq!(
-            Ok((
-                "",
-                vec![
-                    vec![None, Some(Crate('Z')), Some(Crate('V'))],
-                    vec![Some(Crate('A')), Some(Crate('B')), None],
-                ]
-            )),
-            crate_stacks("    [Z] [V]\n[A] [B]    ")
-        )
+            &[vec![krate("A")], vec![], vec![krate("C")]],
+            stacks.stacks()
+        );
+        let (_, stacks) = drawing("[A] [B]\n      1   2\n").unwrap();
+        assert_eq!(&[vec![krate("A")], vec![krate("B")]], stacks.stacks());
+    }
+
+    #[test]
+    fn drawing_reads_wide_first_stacks() {
+        let (_, stacks) = drawing("[ABCDE] [B]\n   1     2 ").unwrap();
+        assert_eq!(&[vec![krate("ABCDE")], vec![krate("B")]], stacks.stacks());
+        let input = "[ABCDE] [B]\n   1     2 \n\nmove 1 from 2 to 1";
+        assert_eq!(Ok("B ".to_string()), part1(input));
+    }
+
+    #[test]
+    fn drawing_errors() {
+        // crate between two labels
+        assert!(drawing("  [A]\n 1   2\n").is_err());
+        // labels out of order
+        assert!(drawing("[A] [B]\n 2   1\n").is_err());
     }
 
     #[test]
@@ -242,4 +440,98 @@ move 1 from 1 to 2";
             moves("move 1 from 3 to 4\nmove 3 from 1 to 6")
         )
     }
+
+    #[test]
+    fn mov_rejects_stack_zero() {
+        assert!(mov("move 1 from 0 to 1").is_err());
+        assert_eq!(
+            "move 3 from 9 to 1",
+            mov("move 3 from 9 to 1").unwrap().1.to_string()
+        );
+    }
+
+    #[test]
+    fn rearrange_errors() {
+        let input = INPUT.replace("move 3 from 1 to 3", "move 3 from 1 to 0");
+        assert_eq!(Err(Error::Parse { line: 7 }), part1(&input));
+
+        let input = INPUT.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let err = part1(&input).unwrap_err();
+        assert_eq!(
+            "line 7: can't move 4 from 1 to 3: stack 1 only holds [Z] [N] [D]",
+            err.to_string()
+        );
+
+        let input = INPUT.replace("move 3 from 1 to 3", "move 1 from 1 to 4");
+        let err = part2(&input).unwrap_err();
+        assert_eq!(
+            "line 7: can't move 1 from 1 to 4: there are only 3 stacks",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn empty_stacks_get_a_placeholder() {
+        let input = INPUT.replace("move 1 from 1 to 2", "move 2 from 1 to 3");
+        assert_eq!(Ok("  C".to_string()), part1(&input));
+        assert_eq!(
+            Ok("--C".to_string()),
+            rearrange(&input, &CrateMover9001, "-")
+        );
+    }
+
+    #[test]
+    fn frames_show_each_move() {
+        let mut frames = String::new();
+        rearrange_with(
+            "[A]\n 1   2\n\nmove 1 from 1 to 2",
+            &CrateMover9000,
+            " ",
+            |mov, stacks| frames.push_str(&frame(mov, stacks)),
+        )
+        .unwrap();
+        assert_eq!(
+            "[A]    \n 1   2 \n\nmove 1 from 1 to 2\n    [A]\n 1   2 \n\n",
+            frames
+        );
+    }
+
+    #[test]
+    fn numbers_row_works() {
+        assert_eq!(
+            Ok(("\n", vec![1..2, 5..6, 9..10])),
+            numbers_row(" 1   2   3 \n")
+        );
+        let (_, labels) = numbers_row(" 1   2   3   4   5   6   7   8   9   10  11").unwrap();
+        assert_eq!((11, 37..39), (labels.len(), labels[9].clone()));
+        assert!(numbers_row(" 1   3 ").is_err());
+        assert!(numbers_row("").is_err());
+    }
+
+    #[test]
+    fn drawing_round_trips() {
+        let (rest, stacks) = drawing(INPUT).unwrap();
+        assert!(rest.starts_with("\n\nmove"));
+        let text = stacks.to_string();
+        assert_eq!(INPUT.split("\n\n").next().unwrap(), text);
+        assert_eq!(Ok(("", stacks)), drawing(&text));
+    }
+
+    fn stacks(rng: &mut Rng) -> Stacks {
+        let stacks = rng.vec(1..15, |rng| {
+            rng.vec(0..6, |rng| {
+                Crate(rng.string("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789", 1..10))
+            })
+        });
+        Stacks::new(stacks)
+    }
+
+    #[test]
+    fn render_round_trips() {
+        check(|rng| {
+            let stacks = stacks(rng);
+            let text = stacks.to_string();
+            assert_eq!(Ok(("", stacks)), drawing(&text));
+        });
+    }
 }
@@ -0,0 +1,268 @@
+use std::{fmt, mem};
+
+use crate::{Crane, Crate, Move};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The move names a stack past the last one; `stacks` is how many there are.
+    NoSuchStack { stacks: usize },
+    /// The source stack holds fewer crates than the move wants; these are the
+    /// ones it does hold, bottom first.
+    NotEnoughCrates { crates: Vec<Crate> },
+}
+
+/// Why a move couldn't be carried out.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MoveError {
+    pub mov: Move,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "can't {}: ", self.mov)?;
+        match &self.kind {
+            ErrorKind::NoSuchStack { stacks } => write!(f, "there are only {} stacks", stacks),
+            ErrorKind::NotEnoughCrates { crates } if crates.is_empty() => {
+                write!(f, "stack {} is empty", self.mov.from + 1)
+            }
+            ErrorKind::NotEnoughCrates { crates } => {
+                write!(f, "stack {} only holds", self.mov.from + 1)?;
+                for krate in crates {
+                    write!(f, " {}", krate)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// The stacks of crates, each listed from the bottom up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stacks {
+    stacks: Vec<Vec<Crate>>,
+}
+
+impl Stacks {
+    /// Takes each stack listed from the bottom up.
+    pub fn new(stacks: Vec<Vec<Crate>>) -> Self {
+        Stacks { stacks }
+    }
+
+    /// Builds `count` stacks from the rows of the drawing, top row first.
+    pub fn from_rows(count: usize, rows: &[Vec<Option<Crate>>]) -> Self {
+        let mut stacks = vec![Vec::new(); count];
+        for row in rows.iter().rev() {
+            for (stack, slot) in stacks.iter_mut().zip(row) {
+                if let Some(krate) = slot {
+                    stack.push(krate.clone());
+                }
+            }
+        }
+        Stacks { stacks }
+    }
+
+    pub fn stacks(&self) -> &[Vec<Crate>] {
+        &self.stacks
+    }
+
+    /// Carries out one move with `crane`, leaving the stacks untouched if
+    /// the move isn't possible.
+    pub fn apply(&mut self, mov: &Move, crane: &impl Crane) -> Result<(), MoveError> {
+        let (from, to, amount) = (mov.from as usize, mov.to as usize, mov.amount as usize);
+        let error = |kind| Err(MoveError { mov: *mov, kind });
+        if from.max(to) >= self.stacks.len() {
+            return error(ErrorKind::NoSuchStack {
+                stacks: self.stacks.len(),
+            });
+        }
+        if self.stacks[from].len() < amount {
+            return error(ErrorKind::NotEnoughCrates {
+                crates: self.stacks[from].clone(),
+            });
+        }
+
+        // take the source stack out so the crane can hold both at once
+        let mut source = mem::take(&mut self.stacks[from]);
+        if from == to {
+            let mut lifted = Vec::new();
+            crane.transfer(&mut source, &mut lifted, amount);
+            crane.transfer(&mut lifted, &mut source, amount);
+        } else {
+            crane.transfer(&mut source, &mut self.stacks[to], amount);
+        }
+        self.stacks[from] = source;
+        Ok(())
+    }
+
+    /// The crate on top of each stack, with a space for empty stacks.
+    pub fn tops(&self) -> String {
+        self.tops_or(" ")
+    }
+
+    /// The crate on top of each stack, with `placeholder` for empty stacks.
+    pub fn tops_or(&self, placeholder: &str) -> String {
+        let mut tops = String::new();
+        for stack in &self.stacks {
+            match stack.last() {
+                Some(Crate(name)) => tops.push_str(name),
+                None => tops.push_str(placeholder),
+            }
+        }
+        tops
+    }
+}
+
+/// Draws the stacks like the puzzle input: crates as `[A]` in columns with the
+/// stack numbers underneath. Lines keep their trailing spaces and there is no
+/// final newline.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let labels = (1..=self.stacks.len())
+            .map(|number| number.to_string())
+            .collect::<Vec<_>>();
+        // each column is as wide as its widest crate or label
+        let widths = self
+            .stacks
+            .iter()
+            .zip(&labels)
+            .map(|(stack, label)| {
+                stack
+                    .iter()
+                    .map(|krate| krate.to_string().len())
+                    .fold(label.len().max(3), usize::max)
+            })
+            .collect::<Vec<_>>();
+
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            for (idx, (stack, &width)) in self.stacks.iter().zip(&widths).enumerate() {
+                if idx > 0 {
+                    write!(f, " ")?;
+                }
+                let slot = stack.get(level).map(Crate::to_string).unwrap_or_default();
+                write!(f, "{:^width$}", slot, width = width)?;
+            }
+            writeln!(f)?;
+        }
+        for (idx, (label, &width)) in labels.iter().zip(&widths).enumerate() {
+            if idx > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:^width$}", label, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CrateMover9000, CrateMover9001};
+
+    fn krate(name: &str) -> Crate {
+        Crate(name.to_string())
+    }
+
+    fn stacks() -> Stacks {
+        let a = Some(krate("A"));
+        let b = Some(krate("B"));
+        let c = Some(krate("C"));
+        Stacks::from_rows(3, &[vec![None, c], vec![a, b]])
+    }
+
+    #[test]
+    fn from_rows_works() {
+        assert_eq!(
+            &[vec![krate("A")], vec![krate("B"), krate("C")], vec![]],
+            stacks().stacks()
+        );
+    }
+
+    #[test]
+    fn apply_works() {
+        let mov = Move {
+            amount: 2,
+            from: 1,
+            to: 2,
+        };
+        let mut stacks = stacks();
+        stacks.apply(&mov, &CrateMover9000).unwrap();
+        assert_eq!(&[krate("C"), krate("B")], stacks.stacks()[2].as_slice());
+        assert!(stacks.stacks()[1].is_empty());
+
+        let mut stacks = self::stacks();
+        stacks.apply(&mov, &CrateMover9001).unwrap();
+        assert_eq!(&[krate("B"), krate("C")], stacks.stacks()[2].as_slice());
+    }
+
+    #[test]
+    fn moving_onto_the_same_stack_keeps_it() {
+        let mut stacks = stacks();
+        let mov = Move {
+            amount: 2,
+            from: 1,
+            to: 1,
+        };
+        stacks.apply(&mov, &CrateMover9000).unwrap();
+        assert_eq!(&[krate("B"), krate("C")], stacks.stacks()[1].as_slice());
+    }
+
+    #[test]
+    fn invalid_moves_leave_stacks_alone() {
+        let mut stacks = stacks();
+        let mov = Move {
+            amount: 2,
+            from: 0,
+            to: 1,
+        };
+        assert_eq!(
+            Err(MoveError {
+                mov,
+                kind: ErrorKind::NotEnoughCrates {
+                    crates: vec![krate("A")]
+                }
+            }),
+            stacks.apply(&mov, &CrateMover9000)
+        );
+        let mov = Move {
+            amount: 0,
+            from: 0,
+            to: 3,
+        };
+        assert_eq!(
+            Err(MoveError {
+                mov,
+                kind: ErrorKind::NoSuchStack { stacks: 3 }
+            }),
+            stacks.apply(&mov, &CrateMover9000)
+        );
+        assert_eq!(self::stacks(), stacks);
+    }
+
+    #[test]
+    fn tops_works() {
+        assert_eq!("AC ", stacks().tops());
+        assert_eq!("AC-", stacks().tops_or("-"));
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!(
+            "    [C]    \n[A] [B]    \n 1   2   3 ",
+            stacks().to_string()
+        );
+        let mut wide = vec![vec![]; 10];
+        wide[9].push(krate("Q"));
+        assert_eq!(
+            format!(
+                "{}[Q]\n{}",
+                " ".repeat(36),
+                " 1   2   3   4   5   6   7   8   9  10 "
+            ),
+            Stacks::new(wide).to_string()
+        );
+    }
+}
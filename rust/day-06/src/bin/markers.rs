@@ -0,0 +1,71 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::process;
+
+use aoc_args::Args;
+use day_06::Marker;
+
+const USAGE: &str = "Usage: markers [--all LEN] [FILE]
+
+Streams the signal in FILE, or input.txt, and prints where the start-of-packet
+and start-of-message markers end as soon as each is found. A FILE of - reads
+standard input.
+
+  --all LEN  print where every marker of LEN distinct bytes ends instead";
+
+struct Options {
+    all: Option<usize>,
+    path: String,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        all: None,
+        path: "input.txt".to_string(),
+    };
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--all" => match args.parse(arg)? {
+                0 => return Err(format!("{} must be a positive number", arg)),
+                len => options.all = Some(len),
+            },
+            _ if arg.starts_with("--") => return Err(aoc_args::unknown(arg)),
+            _ => options.path = arg.to_string(),
+        }
+    }
+    Ok(options)
+}
+
+fn main() {
+    let args = aoc_args::from_env();
+    let options = parse_args(&args).unwrap_or_else(|err| aoc_args::usage_error(err, USAGE));
+
+    let reader: Box<dyn Read> = if options.path == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        match File::open(&options.path) {
+            Ok(file) => Box::new(file),
+            Err(err) => fail(&options.path, err),
+        }
+    };
+
+    if let Some(len) = options.all {
+        for marker in day_06::markers(reader, len) {
+            let end = marker.unwrap_or_else(|err| fail(&options.path, err));
+            println!("{}", end);
+        }
+    } else {
+        for marker in day_06::start_markers(reader) {
+            match marker.unwrap_or_else(|err| fail(&options.path, err)) {
+                Marker::StartOfPacket(end) => println!("start-of-packet: {}", end),
+                Marker::StartOfMessage(end) => println!("start-of-message: {}", end),
+            }
+        }
+    }
+}
+
+fn fail(path: &str, err: io::Error) -> ! {
+    eprintln!("{}: {}", path, err);
+    process::exit(1);
+}
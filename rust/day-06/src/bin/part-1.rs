@@ -1,6 +1,12 @@
-use std::fs;
+use std::{fs, process};
 
 fn main() {
     let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", day_06::part1(&input));
+    match day_06::part1(&input) {
+        Some(marker) => println!("{}", marker),
+        None => {
+            eprintln!("input.txt: no start-of-packet marker");
+            process::exit(1);
+        }
+    }
 }
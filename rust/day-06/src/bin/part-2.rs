@@ -1,6 +1,12 @@
-use std::fs;
+use std::{fs, process};
 
 fn main() {
     let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", day_06::part2(&input));
+    match day_06::part2(&input) {
+        Some(marker) => println!("{}", marker),
+        None => {
+            eprintln!("input.txt: no start-of-message marker");
+            process::exit(1);
+        }
+    }
 }
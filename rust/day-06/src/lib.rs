@@ -1,36 +1,83 @@
-use std::collections::HashSet;
+#[cfg(test)]
+mod naive;
+mod stream;
+
+pub use stream::{markers, start_markers, Detector, Marker, Markers, StartMarkers};
 
-pub fn part1(input: &str) -> usize {
+/// How many distinct bytes make a start-of-packet marker.
+pub const PACKET_MARKER: usize = 4;
+/// How many distinct bytes make a start-of-message marker.
+pub const MESSAGE_MARKER: usize = 14;
+
+/// Where the first marker of `len` distinct bytes ends, i.e. how many bytes
+/// have been read when it's complete, or `None` if there isn't one.
+///
+/// The [`Detector`] tracks the run of distinct bytes ending at each position,
+/// so it's a single pass for any `len`.
+pub fn find_marker(input: &str, len: usize) -> Option<usize> {
+    if len == 0 {
+        return Some(0);
+    }
+    let mut detector = Detector::new(len);
     input
-        .as_bytes()
-        .windows(4)
-        .position(|window| window.iter().collect::<HashSet<&u8>>().len() == 4)
-        .unwrap()
-        + 4
+        .bytes()
+        .position(|byte| detector.push(byte))
+        .map(|idx| idx + 1)
 }
 
-pub fn part2(input: &str) -> usize {
-    input
-        .as_bytes()
-        .windows(14)
-        .position(|window| window.iter().collect::<HashSet<&u8>>().len() == 14)
-        .unwrap()
-        + 14
+/// Where the start-of-packet marker ends.
+pub fn part1(input: &str) -> Option<usize> {
+    find_marker(input, PACKET_MARKER)
+}
+
+/// Where the start-of-message marker ends.
+pub fn part2(input: &str) -> Option<usize> {
+    find_marker(input, MESSAGE_MARKER)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_check::check;
+
+    const EXAMPLES: [(&str, usize, usize); 5] = [
+        ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
 
     #[test]
     fn part1_works() {
-        assert_eq!(part1("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
-        assert_eq!(part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+        for (input, packet, _) in EXAMPLES {
+            assert_eq!(part1(input), Some(packet), "{}", input);
+        }
     }
 
     #[test]
     fn part2_works() {
-        assert_eq!(part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!(part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
+        for (input, _, message) in EXAMPLES {
+            assert_eq!(part2(input), Some(message), "{}", input);
+        }
+    }
+
+    #[test]
+    fn missing_markers() {
+        assert_eq!(part1("abcabcabc"), None);
+        assert_eq!(part1("abc"), None);
+        assert_eq!(part1(""), None);
+        assert_eq!(find_marker("", 0), Some(0));
+        assert_eq!(find_marker("aab", 1), Some(1));
+    }
+
+    /// The naive parts panic without a marker, so every input ends in one.
+    #[test]
+    fn parts_match_naive() {
+        check(|rng| {
+            let input = rng.string("abcdefghijklmnop", 0..61) + "abcdefghijklmn";
+            assert_eq!(Some(naive::part1(&input)), part1(&input));
+            assert_eq!(Some(naive::part2(&input)), part2(&input));
+        });
     }
 }
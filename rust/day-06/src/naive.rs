@@ -0,0 +1,21 @@
+//! The original `HashSet`-per-window parts, which panic without a marker.
+
+use std::collections::HashSet;
+
+pub fn part1(input: &str) -> usize {
+    input
+        .as_bytes()
+        .windows(4)
+        .position(|window| window.iter().collect::<HashSet<&u8>>().len() == 4)
+        .unwrap()
+        + 4
+}
+
+pub fn part2(input: &str) -> usize {
+    input
+        .as_bytes()
+        .windows(14)
+        .position(|window| window.iter().collect::<HashSet<&u8>>().len() == 14)
+        .unwrap()
+        + 14
+}
@@ -0,0 +1,252 @@
+//! Marker detection over any [`Read`], a byte at a time, for signals too big
+//! to load. Readers are wrapped in a [`BufReader`], so bytes are still read
+//! from them in blocks.
+
+use std::io::{self, BufReader, Bytes, Read};
+
+use crate::{MESSAGE_MARKER, PACKET_MARKER};
+
+/// Watches a signal one byte at a time for runs of `len` distinct bytes.
+#[derive(Clone, Debug)]
+pub struct Detector {
+    len: usize,
+    /// One past the index of each byte's last occurrence, 0 if unseen.
+    last_seen: [usize; 256],
+    /// Where the current run of distinct bytes starts.
+    run_start: usize,
+    read: usize,
+}
+
+impl Detector {
+    /// # Panics
+    ///
+    /// If `len` is 0.
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "markers need at least one byte");
+        Detector {
+            len,
+            last_seen: [0; 256],
+            run_start: 0,
+            read: 0,
+        }
+    }
+
+    /// Reads the next byte, returning whether it completes a marker: the last
+    /// `len` bytes, this one included, are all different.
+    pub fn push(&mut self, byte: u8) -> bool {
+        self.run_start = self.run_start.max(self.last_seen[byte as usize]);
+        self.read += 1;
+        self.last_seen[byte as usize] = self.read;
+        self.read - self.run_start >= self.len
+    }
+
+    /// How many bytes have been pushed so far.
+    pub fn read(&self) -> usize {
+        self.read
+    }
+}
+
+/// Where every marker of one length ends, as returned by [`markers`].
+pub struct Markers<R> {
+    bytes: Bytes<BufReader<R>>,
+    detector: Detector,
+}
+
+/// Lists where each marker of `len` distinct bytes in `reader` ends, in
+/// order. Markers can overlap, so a long run of distinct bytes ends one at
+/// every byte from its `len`th on.
+///
+/// # Panics
+///
+/// If `len` is 0.
+pub fn markers<R: Read>(reader: R, len: usize) -> Markers<R> {
+    Markers {
+        bytes: BufReader::new(reader).bytes(),
+        detector: Detector::new(len),
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.bytes.next()? {
+                Ok(byte) if self.detector.push(byte) => return Some(Ok(self.detector.read())),
+                Ok(_) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// A marker found by [`start_markers`], with the number of bytes read up to
+/// and including its last one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Marker {
+    StartOfPacket(usize),
+    StartOfMessage(usize),
+}
+
+/// The start-of-packet and start-of-message markers, as returned by
+/// [`start_markers`].
+pub struct StartMarkers<R> {
+    bytes: Bytes<BufReader<R>>,
+    packet: Option<Detector>,
+    message: Option<Detector>,
+}
+
+/// Reads `reader` until it has found where the start-of-packet and then the
+/// start-of-message marker end, handing each out as soon as it's found.
+/// Stops reading after the start-of-message marker.
+pub fn start_markers<R: Read>(reader: R) -> StartMarkers<R> {
+    StartMarkers {
+        bytes: BufReader::new(reader).bytes(),
+        packet: Some(Detector::new(PACKET_MARKER)),
+        message: Some(Detector::new(MESSAGE_MARKER)),
+    }
+}
+
+impl<R: Read> Iterator for StartMarkers<R> {
+    type Item = io::Result<Marker>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(message) = &mut self.message {
+            let byte = match self.bytes.next()? {
+                Ok(byte) => byte,
+                Err(err) => return Some(Err(err)),
+            };
+            // any run long enough for a message was long enough for a packet
+            // some bytes earlier, so the two never end on the same byte
+            if let Some(packet) = &mut self.packet {
+                if packet.push(byte) {
+                    let read = packet.read();
+                    self.packet = None;
+                    message.push(byte);
+                    return Some(Ok(Marker::StartOfPacket(read)));
+                }
+            }
+            if message.push(byte) {
+                let read = message.read();
+                self.message = None;
+                return Some(Ok(Marker::StartOfMessage(read)));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_marker, part1, part2};
+    use aoc_check::check;
+
+    /// Fails on the read after `ok` bytes.
+    struct Broken<'a> {
+        ok: &'a [u8],
+    }
+
+    impl Read for Broken<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.ok.is_empty() {
+                return Err(io::Error::other("broken"));
+            }
+            let len = buf.len().min(self.ok.len());
+            buf[..len].copy_from_slice(&self.ok[..len]);
+            self.ok = &self.ok[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn markers_works() {
+        let all = markers("mjqjpqmgbljsp".as_bytes(), 4)
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(vec![7, 8, 9, 10, 11, 12, 13], all);
+        assert_eq!(0, markers("aaaa".as_bytes(), 2).count());
+    }
+
+    #[test]
+    fn start_markers_works() {
+        let found = start_markers("mjqjpqmgbljsphdztnvjfqwrcgsmlb".as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            vec![Marker::StartOfPacket(7), Marker::StartOfMessage(19)],
+            found
+        );
+        let found = start_markers("abcabcd".as_bytes())
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Marker::StartOfPacket(7)], found);
+    }
+
+    #[test]
+    fn stops_reading_after_the_message() {
+        let mut found = start_markers(Broken {
+            ok: b"abcdefghijklmn",
+        });
+        assert_eq!(Marker::StartOfPacket(4), found.next().unwrap().unwrap());
+        assert_eq!(Marker::StartOfMessage(14), found.next().unwrap().unwrap());
+        assert!(found.next().is_none());
+    }
+
+    #[test]
+    fn passes_on_read_errors() {
+        let mut found = markers(Broken { ok: b"abcd" }, 4);
+        assert_eq!(4, found.next().unwrap().unwrap());
+        assert!(found.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn first_marker_matches_find_marker() {
+        check(|rng| {
+            let input = rng.string("abcdef", 0..41);
+            let len = rng.range(1..8);
+            let first = markers(input.as_bytes(), len).next().transpose().unwrap();
+            assert_eq!(find_marker(&input, len), first);
+        });
+    }
+
+    #[test]
+    fn finds_every_window() {
+        check(|rng| {
+            let input = rng.string("abcdef", 0..41);
+            let len = rng.range(1..8);
+            let all = markers(input.as_bytes(), len)
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            let distinct = input
+                .as_bytes()
+                .windows(len)
+                .enumerate()
+                .filter(|(_, window)| {
+                    window
+                        .iter()
+                        .enumerate()
+                        .all(|(idx, byte)| !window[..idx].contains(byte))
+                })
+                .map(|(idx, _)| idx + len)
+                .collect::<Vec<_>>();
+            assert_eq!(distinct, all);
+        });
+    }
+
+    #[test]
+    fn start_markers_match_parts() {
+        check(|rng| {
+            let input = rng.string("abcdefghijklmnop", 0..61);
+            let found = start_markers(input.as_bytes())
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            let expected = part1(&input)
+                .map(Marker::StartOfPacket)
+                .into_iter()
+                .chain(part2(&input).map(Marker::StartOfMessage))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, found);
+        });
+    }
+}
@@ -0,0 +1,97 @@
+use aoc_grid::{Direction, Grid, Pos};
+
+#[cfg(test)]
+mod naive;
+
+fn parse_grid(input: &str) -> Grid<u32> {
+    Grid::from_chars(input, |_, c| c.to_digit(10)).unwrap()
+}
+
+pub fn part1(input: &str) -> usize {
+    let grid = parse_grid(input);
+
+    // a tree is visible if every tree between it and some edge is shorter;
+    // trees on the edge have an empty ray and so are always visible
+    grid.iter()
+        .filter(|&(pos, tree_height)| {
+            Direction::CARDINAL
+                .into_iter()
+                .any(|dir| grid.ray(pos, dir).all(|(_, tree)| tree < tree_height))
+        })
+        .count()
+}
+
+/// Number of trees seen from `pos` looking in `dir`, stopping at the first
+/// tree at least as tall as the one at `pos`.
+fn viewing_distance(grid: &Grid<u32>, pos: Pos, dir: Direction) -> usize {
+    let current_tree = grid[pos];
+    let mut distance = 0;
+    for (_, &tree) in grid.ray(pos, dir) {
+        distance += 1;
+        if tree >= current_tree {
+            break;
+        }
+    }
+    distance
+}
+
+pub fn part2(input: &str) -> usize {
+    let grid = parse_grid(input);
+
+    grid.positions()
+        .map(|pos| {
+            Direction::CARDINAL
+                .into_iter()
+                .map(|dir| viewing_distance(&grid, pos, dir))
+                .product()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_check::{check, Rng};
+
+    const INPUT: &str = "30373
+25512
+65332
+33549
+35390";
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(21, part1(INPUT));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(8, part2(INPUT));
+    }
+
+    #[test]
+    fn rectangles_work() {
+        // the first three rows of the example, and the same turned on its side
+        for forest in ["30373\n25512\n65332", "326\n055\n353\n713\n322"] {
+            assert_eq!(14, part1(forest));
+            assert_eq!(2, part2(forest));
+        }
+    }
+
+    /// Square forests, as the naive solution assumes.
+    fn forest(rng: &mut Rng) -> String {
+        let size = rng.range(1..9);
+        let rows = (0..size).map(|_| rng.string("0123456789", size..size + 1));
+        rows.collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn matches_naive() {
+        check(|rng| {
+            let input = forest(rng);
+            assert_eq!(naive::part1(&input) as usize, part1(&input));
+            assert_eq!(naive::part2(&input) as usize, part2(&input));
+        });
+    }
+}
@@ -0,0 +1,61 @@
+mod moves;
+pub mod render;
+mod rope;
+
+pub use moves::{parse_moves, ErrorKind, Move, ParseError};
+pub use rope::{Adjacency, Rope};
+
+/// Runs every move on `rope`.
+pub fn simulate(rope: &mut Rope, moves: &[Move]) {
+    for mov in moves {
+        for _ in 0..mov.count {
+            rope.step(mov.dir);
+        }
+    }
+}
+
+/// Number of positions the tail of a `knot_count` knot rope visits.
+pub fn tail_positions(moves: &[Move], knot_count: usize, adjacency: Adjacency) -> usize {
+    let mut rope = Rope::with_adjacency(knot_count, adjacency);
+    simulate(&mut rope, moves);
+    rope.tail_visited().len()
+}
+
+pub fn part1(moves: &[Move]) -> usize {
+    tail_positions(moves, 2, Adjacency::Chebyshev)
+}
+
+pub fn part2(moves: &[Move]) -> usize {
+    tail_positions(moves, 10, Adjacency::Chebyshev)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_works() {
+        let input = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+        assert_eq!(13, part1(&parse_moves(input).unwrap()));
+    }
+
+    #[test]
+    fn part2_works() {
+        let input = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+        assert_eq!(36, part2(&parse_moves(input).unwrap()));
+    }
+}
@@ -1,134 +1,116 @@
-use std::collections::BTreeSet;
-use std::fs;
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process;
+
+use aoc_args::Args;
+use day_09::render::{self, Bounds, Granularity};
+use day_09::{Adjacency, Move, Rope};
+
+const USAGE: &str = "Usage: day-09 [--adjacency RULE] [--knots N] [--per-line] [--render]
+              [--asciicast FILE] [--ppm DIR]
+
+Without --render, --asciicast or --ppm, prints the answers to both parts, or
+with --knots how many positions the tail of that rope visits.
+
+  --adjacency RULE  when knots touch: chebyshev (default) or manhattan
+  --knots N         simulate a rope of N knots (animations default to 10)
+  --per-line        only keep a frame at the end of each input line; needs
+                    --render, --asciicast or --ppm
+  --render          print every frame to stdout
+  --asciicast FILE  write the animation as an asciicast v2 recording
+  --ppm DIR         write every frame as DIR/frame-NNNNN.ppm";
+
+struct Options {
+    adjacency: Adjacency,
+    knots: Option<usize>,
+    granularity: Granularity,
+    render: bool,
+    asciicast: Option<PathBuf>,
+    ppm: Option<PathBuf>,
 }
 
-fn part1(input: &str) -> usize {
-    let mut head = (0, 0);
-    let mut tail = (0, 0);
-    let mut tail_positions = BTreeSet::from([tail]);
-
-    for line in input.lines() {
-        let (dir, count) = line.split_once(' ').unwrap();
-        let count = count.parse::<i32>().unwrap();
-
-        for _ in 0..count {
-            head = match dir {
-                "U" => (head.0, head.1 + 1),
-                "D" => (head.0, head.1 - 1),
-                "R" => (head.0 + 1, head.1),
-                "L" => (head.0 - 1, head.1),
-                _ => unreachable!(),
-            };
-
-            let h: i32 = head.0 - tail.0;
-            let v: i32 = head.1 - tail.1;
-
-            if h.abs() > 1 || v.abs() > 1 {
-                if h == 0 && v.abs() > 1 {
-                    let dy = if v > 1 { 1 } else { -1 };
-                    tail = (tail.0, tail.1 + dy);
-                } else if h.abs() > 1 && v == 0 {
-                    let dx = if h > 1 { 1 } else { -1 };
-                    tail = (tail.0 + dx, tail.1);
-                } else {
-                    let dx = if h > 0 { 1 } else { -1 };
-                    let dy = if v > 0 { 1 } else { -1 };
-                    tail = (tail.0 + dx, tail.1 + dy);
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        adjacency: Adjacency::Chebyshev,
+        knots: None,
+        granularity: Granularity::Step,
+        render: false,
+        asciicast: None,
+        ppm: None,
+    };
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--adjacency" => {
+                options.adjacency = match args.value(arg)? {
+                    "chebyshev" => Adjacency::Chebyshev,
+                    "manhattan" => Adjacency::Manhattan,
+                    rule => return Err(format!("unknown adjacency rule {}", rule)),
                 }
             }
-
-            tail_positions.insert(tail);
+            "--knots" => match args.parse(arg)? {
+                0 => return Err(format!("{} must be a positive number", arg)),
+                knots => options.knots = Some(knots),
+            },
+            "--per-line" => options.granularity = Granularity::Line,
+            "--render" => options.render = true,
+            "--asciicast" => options.asciicast = Some(args.value(arg)?.into()),
+            "--ppm" => options.ppm = Some(args.value(arg)?.into()),
+            _ => return Err(aoc_args::unknown(arg)),
         }
     }
-    tail_positions.len()
-}
-
-fn part2(input: &str) -> usize {
-    let mut knots = Vec::new();
-    for _ in 0..10 {
-        knots.push((0, 0));
+    let animated = options.render || options.asciicast.is_some() || options.ppm.is_some();
+    if options.granularity == Granularity::Line && !animated {
+        return Err("--per-line needs --render, --asciicast or --ppm".to_string());
     }
-    let mut tail_positions = BTreeSet::from([(0, 0)]);
-
-    for line in input.lines() {
-        let (dir, count) = line.split_once(' ').unwrap();
-        let count = count.parse::<i32>().unwrap();
-
-        for _ in 0..count {
-            for i in 1..knots.len() {
-                let mut parent = knots[i - 1];
-                let mut current = knots[i];
-                if i == 1 {
-                    parent = match dir {
-                        "U" => (parent.0, parent.1 + 1),
-                        "D" => (parent.0, parent.1 - 1),
-                        "R" => (parent.0 + 1, parent.1),
-                        "L" => (parent.0 - 1, parent.1),
-                        _ => unreachable!(),
-                    };
-                    knots[i - 1] = parent;
-                }
-
-                let h: i32 = parent.0 - current.0;
-                let v: i32 = parent.1 - current.1;
-
-                if h.abs() > 1 || v.abs() > 1 {
-                    if h == 0 && v.abs() > 1 {
-                        let dy = if v > 1 { 1 } else { -1 };
-                        current = (current.0, current.1 + dy);
-                    } else if h.abs() > 1 && v == 0 {
-                        let dx = if h > 1 { 1 } else { -1 };
-                        current = (current.0 + dx, current.1);
-                    } else {
-                        let dx = if h > 0 { 1 } else { -1 };
-                        let dy = if v > 0 { 1 } else { -1 };
-                        current = (current.0 + dx, current.1 + dy);
-                    }
-                }
+    Ok(options)
+}
 
-                knots[i] = current;
+fn main() {
+    let args = aoc_args::from_env();
+    let options = parse_args(&args).unwrap_or_else(|err| aoc_args::usage_error(err, USAGE));
 
-                if i == 9 {
-                    tail_positions.insert(current);
-                }
+    let input = fs::read_to_string("input.txt").unwrap();
+    let moves = day_09::parse_moves(&input).unwrap_or_else(|err| {
+        eprintln!("input.txt: {}", err);
+        process::exit(1);
+    });
+
+    if !options.render && options.asciicast.is_none() && options.ppm.is_none() {
+        let answer = |knots| day_09::tail_positions(&moves, knots, options.adjacency);
+        match options.knots {
+            Some(knots) => println!("{} knots: {}", knots, answer(knots)),
+            None => {
+                println!("Part 1: {}", answer(2));
+                println!("Part 2: {}", answer(10));
             }
         }
+        return;
+    }
+    if let Err(err) = animate(&moves, &options) {
+        eprintln!("{}", err);
+        process::exit(1);
     }
-    tail_positions.len()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn animate(moves: &[Move], options: &Options) -> io::Result<()> {
+    let rope = Rope::with_adjacency(options.knots.unwrap_or(10), options.adjacency);
+    let frames = render::record(rope, moves, options.granularity);
+    let bounds = Bounds::fit(&frames);
 
-    #[test]
-    fn part1_works() {
-        let input = "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2";
-        assert_eq!(13, part1(input));
+    if options.render {
+        let mut out = BufWriter::new(io::stdout().lock());
+        render::write_text(&mut out, &frames, &bounds)?;
+        out.flush()?;
     }
-
-    #[test]
-    fn part2_works() {
-        let input = "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
-        assert_eq!(36, part2(input));
+    if let Some(path) = &options.asciicast {
+        let mut out = BufWriter::new(File::create(path)?);
+        render::write_asciicast(&mut out, &frames, &bounds, 0.1)?;
+        out.flush()?;
+    }
+    if let Some(dir) = &options.ppm {
+        render::write_ppm_frames(dir, &frames, &bounds, 4)?;
     }
+    Ok(())
 }
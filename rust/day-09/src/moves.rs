@@ -0,0 +1,198 @@
+use std::fmt;
+
+use aoc_grid::Direction;
+
+/// One line of the puzzle input: move the head `count` steps in `dir`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub dir: Direction,
+    pub count: usize,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", dir_name(self.dir), self.count)
+    }
+}
+
+fn dir_name(dir: Direction) -> &'static str {
+    match dir {
+        Direction::Up => "U",
+        Direction::Down => "D",
+        Direction::Left => "L",
+        Direction::Right => "R",
+        Direction::UpLeft => "UL",
+        Direction::UpRight => "UR",
+        Direction::DownLeft => "DL",
+        Direction::DownRight => "DR",
+    }
+}
+
+fn parse_dir(name: &str) -> Option<Direction> {
+    let dir = match name.to_ascii_uppercase().as_str() {
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        "UL" => Direction::UpLeft,
+        "UR" => Direction::UpRight,
+        "DL" => Direction::DownLeft,
+        "DR" => Direction::DownRight,
+        _ => return None,
+    };
+    Some(dir)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnknownDirection(String),
+    MissingCount,
+    InvalidCount(String),
+    TrailingInput(String),
+}
+
+/// Why a line couldn't be parsed, with 1-based line and column numbers.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::UnknownDirection(dir) => write!(f, "unknown direction {:?}", dir),
+            ErrorKind::MissingCount => write!(f, "missing step count"),
+            ErrorKind::InvalidCount(count) => write!(f, "invalid step count {:?}", count),
+            ErrorKind::TrailingInput(rest) => write!(f, "unexpected {:?}", rest),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits off the next whitespace-separated word, returning its 1-based
+/// column along with it.
+fn next_word<'a>(line: &'a str, rest: &mut &'a str) -> Option<(usize, &'a str)> {
+    let trimmed = rest.trim_start();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let column = line.len() - trimmed.len() + 1;
+    let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+    let (word, remaining) = trimmed.split_at(end);
+    *rest = remaining;
+    Some((column, word))
+}
+
+/// Parses a single `<dir> <count>` line, reporting errors against `line_number`.
+/// Direction letters are case-insensitive.
+fn parse_line(line_number: usize, line: &str) -> Result<Move, ParseError> {
+    let error = |column, kind| ParseError {
+        line: line_number,
+        column,
+        kind,
+    };
+    let mut rest = line;
+
+    // callers skip blank lines, so there is always a first word
+    let (column, name) = next_word(line, &mut rest).unwrap();
+    let dir = parse_dir(name)
+        .ok_or_else(|| error(column, ErrorKind::UnknownDirection(name.to_string())))?;
+
+    let (column, count) =
+        next_word(line, &mut rest).ok_or_else(|| error(line.len() + 1, ErrorKind::MissingCount))?;
+    let count = count
+        .parse()
+        .map_err(|_| error(column, ErrorKind::InvalidCount(count.to_string())))?;
+
+    if let Some((column, trailing)) = next_word(line, &mut rest) {
+        return Err(error(
+            column,
+            ErrorKind::TrailingInput(trailing.to_string()),
+        ));
+    }
+    Ok(Move { dir, count })
+}
+
+/// Parses one move per line, skipping blank lines.
+pub fn parse_moves(input: &str) -> Result<Vec<Move>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_line_works() {
+        assert_eq!(
+            Ok(Move {
+                dir: Direction::Right,
+                count: 4
+            }),
+            parse_line(1, "R 4")
+        );
+        assert_eq!(
+            Ok(Move {
+                dir: Direction::UpLeft,
+                count: 12
+            }),
+            parse_line(1, "ul  12 ")
+        );
+    }
+
+    #[test]
+    fn parse_line_errors() {
+        let error = |column, kind| {
+            Err(ParseError {
+                line: 3,
+                column,
+                kind,
+            })
+        };
+        assert_eq!(
+            error(2, ErrorKind::UnknownDirection("X".to_string())),
+            parse_line(3, " X 1")
+        );
+        assert_eq!(error(2, ErrorKind::MissingCount), parse_line(3, "R"));
+        assert_eq!(
+            error(3, ErrorKind::InvalidCount("-1".to_string())),
+            parse_line(3, "R -1")
+        );
+        assert_eq!(
+            error(5, ErrorKind::TrailingInput("R".to_string())),
+            parse_line(3, "R 1 R")
+        );
+    }
+
+    #[test]
+    fn parse_moves_works() {
+        let moves = parse_moves("R 4\n\nDR 2\n").unwrap();
+        assert_eq!(
+            vec![
+                Move {
+                    dir: Direction::Right,
+                    count: 4
+                },
+                Move {
+                    dir: Direction::DownRight,
+                    count: 2
+                }
+            ],
+            moves
+        );
+        assert_eq!("DR 2", moves[1].to_string());
+
+        let err = parse_moves("R 4\n\nq 2").unwrap_err();
+        assert_eq!((3, 1), (err.line, err.column));
+        assert_eq!("line 3, column 1: unknown direction \"q\"", err.to_string());
+    }
+}
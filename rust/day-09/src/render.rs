@@ -0,0 +1,350 @@
+//! Drawing the rope the way the puzzle text does, so the simulation can be
+//! compared against its diagrams.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use aoc_grid::{Grid, Point};
+
+use crate::{Move, Rope};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Granularity {
+    /// A frame after every single step of the head.
+    Step,
+    /// A frame only once each input line has been fully applied.
+    Line,
+}
+
+/// The rope as it was at one point of the simulation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// Set on the first frame of each input line, e.g. `"R 4"`.
+    pub caption: Option<String>,
+    pub knots: Vec<Point>,
+}
+
+/// Runs `moves` on `rope`, keeping a frame for the initial state and then as
+/// often as `granularity` asks for.
+pub fn record(mut rope: Rope, moves: &[Move], granularity: Granularity) -> Vec<Frame> {
+    let mut frames = vec![Frame {
+        caption: Some("Initial State".to_string()),
+        knots: rope.knots().to_vec(),
+    }];
+    for mov in moves {
+        let mut caption = Some(mov.to_string());
+        for i in 0..mov.count {
+            rope.step(mov.dir);
+            if granularity == Granularity::Step || i + 1 == mov.count {
+                frames.push(Frame {
+                    caption: caption.take(),
+                    knots: rope.knots().to_vec(),
+                });
+            }
+        }
+    }
+    frames
+}
+
+/// The area a set of frames is drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The smallest rectangle containing the start and every knot of every
+    /// frame, so all frames line up with each other.
+    pub fn fit<'a>(frames: impl IntoIterator<Item = &'a Frame>) -> Bounds {
+        let mut bounds = Bounds {
+            min: Point::ORIGIN,
+            max: Point::ORIGIN,
+        };
+        for knot in frames.into_iter().flat_map(|frame| &frame.knots) {
+            bounds.min = Point::new(bounds.min.x.min(knot.x), bounds.min.y.min(knot.y));
+            bounds.max = Point::new(bounds.max.x.max(knot.x), bounds.max.y.max(knot.y));
+        }
+        bounds
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+}
+
+/// The marker the puzzle uses for knot `index` of a rope with `knot_count`
+/// knots: `H` for the head, `T` for the tail of a two-knot rope and the knot
+/// number otherwise.
+pub fn label(index: usize, knot_count: usize) -> char {
+    match index {
+        0 => 'H',
+        _ if knot_count == 2 => 'T',
+        _ => char::from_digit((index % 36) as u32, 36).unwrap(),
+    }
+}
+
+/// Draws `knots` inside `bounds`. Where knots overlap the one nearer the head
+/// wins, and the start is marked `s` if nothing covers it.
+pub fn draw(knots: &[Point], bounds: &Bounds) -> Grid<char> {
+    let mut grid = Grid::new(bounds.width(), bounds.height(), '.');
+    let cell = |point: Point| {
+        (
+            (point.x - bounds.min.x) as usize,
+            (point.y - bounds.min.y) as usize,
+        )
+    };
+    grid[cell(Point::ORIGIN)] = 's';
+    for (i, knot) in knots.iter().enumerate().rev() {
+        grid[cell(*knot)] = label(i, knots.len());
+    }
+    grid
+}
+
+/// Writes the frames in the puzzle's format: a `== R 4 ==` header per input
+/// line followed by each frame, all separated by blank lines.
+pub fn write_text(out: &mut impl Write, frames: &[Frame], bounds: &Bounds) -> io::Result<()> {
+    for frame in frames {
+        if let Some(caption) = &frame.caption {
+            writeln!(out, "== {} ==\n", caption)?;
+        }
+        writeln!(out, "{}\n", draw(&frame.knots, bounds))?;
+    }
+    Ok(())
+}
+
+/// Writes an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+/// recording that shows one frame every `delay` seconds.
+pub fn write_asciicast(
+    out: &mut impl Write,
+    frames: &[Frame],
+    bounds: &Bounds,
+    delay: f64,
+) -> io::Result<()> {
+    let caption_width = frames
+        .iter()
+        .filter_map(|frame| frame.caption.as_ref())
+        .map(|caption| caption.len() + "==  ==".len())
+        .max()
+        .unwrap_or(0);
+    writeln!(
+        out,
+        r#"{{"version": 2, "width": {}, "height": {}}}"#,
+        bounds.width().max(caption_width),
+        bounds.height() + 2
+    )?;
+
+    let mut caption = "";
+    for (i, frame) in frames.iter().enumerate() {
+        if let Some(new_caption) = &frame.caption {
+            caption = new_caption;
+        }
+        // home the cursor and clear the screen before every frame
+        let screen = format!(
+            "\x1b[H\x1b[2J== {} ==\r\n\r\n{}",
+            caption,
+            draw(&frame.knots, bounds).to_string().replace('\n', "\r\n")
+        );
+        writeln!(
+            out,
+            "[{:.3}, \"o\", {}]",
+            i as f64 * delay,
+            json_string(&screen)
+        )?;
+    }
+    Ok(())
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Writes every frame to `dir` as `frame-00000.ppm`, `frame-00001.ppm`, ...
+pub fn write_ppm_frames(
+    dir: &Path,
+    frames: &[Frame],
+    bounds: &Bounds,
+    scale: usize,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let file = File::create(dir.join(format!("frame-{:05}.ppm", i)))?;
+        let mut out = BufWriter::new(file);
+        write_ppm(&mut out, &draw(&frame.knots, bounds), scale)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Writes a drawing as a binary PPM image with each cell `scale` pixels square.
+pub fn write_ppm(out: &mut impl Write, drawing: &Grid<char>, scale: usize) -> io::Result<()> {
+    writeln!(
+        out,
+        "P6\n{} {}\n255",
+        drawing.width() * scale,
+        drawing.height() * scale
+    )?;
+    for y in 0..drawing.height() {
+        let line = drawing
+            .row(y)
+            .flat_map(|&c| colour(c).repeat(scale))
+            .collect::<Vec<u8>>();
+        for _ in 0..scale {
+            out.write_all(&line)?;
+        }
+    }
+    Ok(())
+}
+
+fn colour(marker: char) -> [u8; 3] {
+    match marker {
+        '.' => [24, 24, 24],
+        's' => [110, 110, 110],
+        'H' => [220, 50, 50],
+        'T' => [60, 120, 230],
+        _ => [230, 170, 40],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_moves;
+
+    fn record(input: &str, knot_count: usize, granularity: Granularity) -> Vec<Frame> {
+        super::record(
+            Rope::new(knot_count),
+            &parse_moves(input).unwrap(),
+            granularity,
+        )
+    }
+
+    const INPUT: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+    #[test]
+    fn label_works() {
+        assert_eq!(
+            vec!['H', 'T'],
+            (0..2).map(|i| label(i, 2)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "H123456789",
+            (0..10).map(|i| label(i, 10)).collect::<String>()
+        );
+    }
+
+    #[test]
+    fn record_works() {
+        let frames = record(INPUT, 2, Granularity::Step);
+        // the initial state plus one frame per step
+        assert_eq!(25, frames.len());
+        assert_eq!(Some("R 4".to_string()), frames[1].caption);
+        assert_eq!(None, frames[2].caption);
+
+        let frames = record(INPUT, 2, Granularity::Line);
+        assert_eq!(9, frames.len());
+        assert!(frames.iter().all(|frame| frame.caption.is_some()));
+    }
+
+    #[test]
+    fn draw_matches_puzzle() {
+        let frames = record(INPUT, 2, Granularity::Line);
+        let bounds = Bounds::fit(&frames);
+        assert_eq!((6, 5), (bounds.width(), bounds.height()));
+        assert_eq!(
+            "......
+......
+......
+......
+H.....",
+            draw(&frames[0].knots, &bounds).to_string()
+        );
+        assert_eq!(
+            "......
+......
+......
+......
+s..TH.",
+            draw(&frames[1].knots, &bounds).to_string()
+        );
+        assert_eq!(
+            "......
+......
+.TH...
+......
+s.....",
+            draw(&frames[8].knots, &bounds).to_string()
+        );
+    }
+
+    #[test]
+    fn write_text_works() {
+        let frames = record("R 2", 2, Granularity::Step);
+        let mut out = Vec::new();
+        write_text(&mut out, &frames, &Bounds::fit(&frames)).unwrap();
+        assert_eq!(
+            "== Initial State ==
+
+H..
+
+== R 2 ==
+
+TH.
+
+sTH
+
+",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_asciicast_works() {
+        let frames = record("R 1", 2, Granularity::Step);
+        let mut out = Vec::new();
+        write_asciicast(&mut out, &frames, &Bounds::fit(&frames), 0.5).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(r#"{"version": 2, "width": 19, "height": 3}"#, lines[0]);
+        assert_eq!(
+            r#"[0.500, "o", "\u001b[H\u001b[2J== R 1 ==\r\n\r\nTH"]"#,
+            lines[2]
+        );
+    }
+
+    #[test]
+    fn write_ppm_works() {
+        let mut out = Vec::new();
+        let drawing = Grid::from_vec(2, vec!['H', '.']);
+        write_ppm(&mut out, &drawing, 2).unwrap();
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(header, &out[..header.len()]);
+        assert_eq!(header.len() + 4 * 2 * 3, out.len());
+        assert_eq!(&colour('H'), &out[header.len()..header.len() + 3]);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeSet;
+
+use aoc_grid::{Direction, Point};
+
+/// When two knots count as touching, which decides how followers move.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Knots touch orthogonally or diagonally, and a follower catches up with
+    /// a single step that may be diagonal. This is the puzzle's rule.
+    #[default]
+    Chebyshev,
+    /// Knots only touch orthogonally, and a follower catches up with as many
+    /// orthogonal steps as it needs.
+    Manhattan,
+}
+
+/// A rope of knots starting at the origin, where each knot follows the one
+/// before it and the first knot is the head.
+#[derive(Clone, Debug)]
+pub struct Rope {
+    knots: Vec<Point>,
+    visited: Vec<BTreeSet<Point>>,
+    adjacency: Adjacency,
+}
+
+impl Rope {
+    /// Panics if `knot_count` is zero.
+    pub fn new(knot_count: usize) -> Rope {
+        Rope::with_adjacency(knot_count, Adjacency::default())
+    }
+
+    /// Panics if `knot_count` is zero.
+    pub fn with_adjacency(knot_count: usize, adjacency: Adjacency) -> Rope {
+        assert!(knot_count > 0, "a rope needs at least one knot");
+        Rope {
+            knots: vec![Point::ORIGIN; knot_count],
+            visited: vec![BTreeSet::from([Point::ORIGIN]); knot_count],
+            adjacency,
+        }
+    }
+
+    /// Moves the head one step in `dir` and lets every other knot catch up.
+    pub fn step(&mut self, dir: Direction) {
+        self.knots[0] += dir;
+        for i in 1..self.knots.len() {
+            self.knots[i] = match self.adjacency {
+                Adjacency::Chebyshev => follow(self.knots[i - 1], self.knots[i]),
+                Adjacency::Manhattan => follow_orthogonally(self.knots[i - 1], self.knots[i]),
+            };
+        }
+        for (visited, knot) in self.visited.iter_mut().zip(&self.knots) {
+            visited.insert(*knot);
+        }
+    }
+
+    pub fn knots(&self) -> &[Point] {
+        &self.knots
+    }
+
+    pub fn head(&self) -> Point {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Point {
+        self.knots[self.knots.len() - 1]
+    }
+
+    /// Every position knot `knot` has occupied, including the origin.
+    /// Panics if `knot` is out of range.
+    pub fn visited(&self, knot: usize) -> &BTreeSet<Point> {
+        &self.visited[knot]
+    }
+
+    pub fn tail_visited(&self) -> &BTreeSet<Point> {
+        &self.visited[self.visited.len() - 1]
+    }
+}
+
+/// Moves `current` one step towards `parent` if they are no longer touching.
+fn follow(parent: Point, current: Point) -> Point {
+    let diff = parent - current;
+    if diff.x.abs() > 1 || diff.y.abs() > 1 {
+        current + diff.signum()
+    } else {
+        current
+    }
+}
+
+/// Moves `current` orthogonally towards `parent` until they touch without
+/// diagonals, closing the larger gap first.
+fn follow_orthogonally(parent: Point, mut current: Point) -> Point {
+    while parent.manhattan(current) > 1 {
+        let diff = parent - current;
+        if diff.x.abs() >= diff.y.abs() {
+            current.x += diff.x.signum();
+        } else {
+            current.y += diff.y.signum();
+        }
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follow_works() {
+        // touching, including overlapping and diagonal
+        assert_eq!(Point::ORIGIN, follow(Point::ORIGIN, Point::ORIGIN));
+        assert_eq!(Point::ORIGIN, follow(Point::new(1, 1), Point::ORIGIN));
+        // straight line
+        assert_eq!(Point::new(1, 0), follow(Point::new(2, 0), Point::ORIGIN));
+        // diagonal catch-up
+        assert_eq!(Point::new(1, -1), follow(Point::new(2, -1), Point::ORIGIN));
+    }
+
+    #[test]
+    fn follow_orthogonally_works() {
+        assert_eq!(
+            Point::ORIGIN,
+            follow_orthogonally(Point::new(0, 1), Point::ORIGIN)
+        );
+        // diagonal neighbours no longer touch
+        assert_eq!(
+            Point::new(1, 0),
+            follow_orthogonally(Point::new(1, 1), Point::ORIGIN)
+        );
+        // a diagonal head move can need two steps to catch up
+        assert_eq!(
+            Point::new(2, 1),
+            follow_orthogonally(Point::new(2, 2), Point::ORIGIN)
+        );
+    }
+
+    #[test]
+    fn rope_works() {
+        let mut rope = Rope::new(3);
+        for _ in 0..3 {
+            rope.step(Direction::Right);
+        }
+        assert_eq!(
+            &[Point::new(3, 0), Point::new(2, 0), Point::new(1, 0)],
+            rope.knots()
+        );
+        assert_eq!(4, rope.visited(0).len());
+        assert_eq!(3, rope.visited(1).len());
+        assert_eq!(2, rope.tail_visited().len());
+    }
+
+    #[test]
+    fn manhattan_rope_works() {
+        let mut rope = Rope::with_adjacency(2, Adjacency::Manhattan);
+        rope.step(Direction::UpRight);
+        assert_eq!(&[Point::new(1, -1), Point::new(1, 0)], rope.knots());
+        rope.step(Direction::UpRight);
+        assert_eq!(&[Point::new(2, -2), Point::new(2, -1)], rope.knots());
+    }
+}
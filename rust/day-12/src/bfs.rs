@@ -0,0 +1,93 @@
+use std::collections::VecDeque;
+
+use aoc_grid::{Grid, Pos};
+
+use crate::{ClimbRule, Nearest};
+
+/// A breadth-first search over a heightmap, keeping the distance to every
+/// reachable cell and the cell it was first reached from.
+#[derive(Clone, Debug)]
+pub struct Bfs {
+    distances: Grid<Option<usize>>,
+    parents: Grid<Option<Pos>>,
+}
+
+impl Bfs {
+    /// Searches outwards from `start`, taking the steps `rule` allows.
+    pub fn new(heightmap: &Grid<i32>, start: Pos, rule: ClimbRule) -> Bfs {
+        Bfs::from_sources(heightmap, &[start], rule)
+    }
+
+    /// Searches outwards from all of `sources` at once, so each cell's
+    /// distance is to whichever source is nearest.
+    pub fn from_sources(heightmap: &Grid<i32>, sources: &[Pos], rule: ClimbRule) -> Bfs {
+        let (width, height) = (heightmap.width(), heightmap.height());
+        let mut distances = Grid::new(width, height, None);
+        let mut parents = Grid::new(width, height, None);
+        let mut queue = VecDeque::with_capacity(width * height);
+
+        for &source in sources {
+            if distances[source].is_none() {
+                distances[source] = Some(0);
+                queue.push_back(source);
+            }
+        }
+        while let Some(pos) = queue.pop_front() {
+            let distance = distances[pos].unwrap();
+            for next in rule.moves(heightmap, pos) {
+                if distances[next].is_some() {
+                    continue;
+                }
+                distances[next] = Some(distance + 1);
+                parents[next] = Some(pos);
+                queue.push_back(next);
+            }
+        }
+
+        Bfs { distances, parents }
+    }
+
+    /// Number of steps from the nearest source to `pos`, if it is reachable.
+    pub fn distance(&self, pos: Pos) -> Option<usize> {
+        self.distances.get(pos).copied().flatten()
+    }
+
+    pub fn distances(&self) -> &Grid<Option<usize>> {
+        &self.distances
+    }
+
+    /// A shortest path from the nearest source to `pos`, including both ends.
+    pub fn path(&self, pos: Pos) -> Option<Vec<Pos>> {
+        let mut path = Vec::with_capacity(self.distance(pos)? + 1);
+        path.push(pos);
+        while let Some(parent) = self.parents[path[path.len() - 1]] {
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Whichever of `targets` is fewest steps from a source, with the source
+    /// it was reached from.
+    pub fn nearest(&self, targets: &[Pos]) -> Option<Nearest> {
+        let (target, steps) = targets
+            .iter()
+            .filter_map(|&target| Some((target, self.distance(target)?)))
+            .min_by_key(|&(_, steps)| steps)?;
+        Some(Nearest {
+            source: self.origin(target)?,
+            target,
+            steps,
+        })
+    }
+
+    /// The source that `pos` was reached from.
+    pub fn origin(&self, pos: Pos) -> Option<Pos> {
+        self.distance(pos)?;
+        let mut current = pos;
+        while let Some(parent) = self.parents[current] {
+            current = parent;
+        }
+        Some(current)
+    }
+}
@@ -0,0 +1,315 @@
+use std::fmt;
+
+use aoc_grid::{Grid, Pos};
+
+mod bfs;
+#[cfg(test)]
+mod naive;
+pub mod pathfinding;
+pub mod render;
+
+pub use bfs::Bfs;
+pub use pathfinding::ClimbRule;
+
+/// The puzzle input: heights from `a` as 0 up to `z` as 25, and every `S`
+/// and `E` marker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heightmap {
+    pub heights: Grid<i32>,
+    pub starts: Vec<Pos>,
+    pub ends: Vec<Pos>,
+}
+
+impl Heightmap {
+    /// Every cell at height `a`, where part 2's route may start.
+    pub fn lowest(&self) -> Vec<Pos> {
+        self.heights
+            .iter()
+            .filter(|(_, &h)| h == 0)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A character other than `a` to `z`, `S` or `E`, or rows of uneven length.
+    Grid(aoc_grid::ParseError),
+    MissingStart,
+    MissingEnd,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Grid(err) => err.fmt(f),
+            ParseError::MissingStart => write!(f, "no start marked with S"),
+            ParseError::MissingEnd => write!(f, "no end marked with E"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<aoc_grid::ParseError> for ParseError {
+    fn from(err: aoc_grid::ParseError) -> Self {
+        ParseError::Grid(err)
+    }
+}
+
+pub fn parse_heightmap(input: &str) -> Result<Heightmap, ParseError> {
+    let mut starts = vec![];
+    let mut ends = vec![];
+    let heights = Grid::from_chars(input, |pos, c| {
+        let height = match c {
+            'a'..='z' => c as u8 - b'a',
+            'S' => {
+                starts.push(pos);
+                0
+            }
+            'E' => {
+                ends.push(pos);
+                b'z' - b'a'
+            }
+            _ => return None,
+        };
+        Some(height as i32)
+    })?;
+    if starts.is_empty() {
+        return Err(ParseError::MissingStart);
+    }
+    if ends.is_empty() {
+        return Err(ParseError::MissingEnd);
+    }
+    Ok(Heightmap {
+        heights,
+        starts,
+        ends,
+    })
+}
+
+/// The closest pair found by [`nearest_pair`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nearest {
+    pub source: Pos,
+    pub target: Pos,
+    pub steps: usize,
+}
+
+/// The source and target with the fewest steps between them, found with a
+/// single search from all sources at once.
+pub fn nearest_pair(
+    heights: &Grid<i32>,
+    sources: &[Pos],
+    targets: &[Pos],
+    rule: ClimbRule,
+) -> Option<Nearest> {
+    Bfs::from_sources(heights, sources, rule).nearest(targets)
+}
+
+/// Fewest steps from any `S` to any `E`, if an `E` can be reached at all.
+pub fn part1(heightmap: &Heightmap) -> Option<usize> {
+    nearest_pair(
+        &heightmap.heights,
+        &heightmap.starts,
+        &heightmap.ends,
+        ClimbRule::PUZZLE,
+    )
+    .map(|nearest| nearest.steps)
+}
+
+/// Fewest steps from any lowest cell to any `E`, if any of them can reach it.
+pub fn part2(heightmap: &Heightmap) -> Option<usize> {
+    let lowest = heightmap.lowest();
+    // search backwards from the ends, so every step is a climb in reverse
+    nearest_pair(
+        &heightmap.heights,
+        &heightmap.ends,
+        &lowest,
+        ClimbRule::PUZZLE.reversed(),
+    )
+    .map(|nearest| nearest.steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_check::{check, Rng};
+
+    pub(crate) const INPUT: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn parse_heightmap_works() {
+        #[rustfmt::skip]
+        let heightmap = Grid::from_vec(8, vec![
+            0, 0, 1, 16, 15, 14, 13, 12,
+            0, 1, 2, 17, 24, 23, 23, 11,
+            0, 2, 2, 18, 25, 25, 23, 10,
+            0, 2, 2, 19, 20, 21, 22, 9,
+            0, 1, 3, 4, 5, 6, 7, 8,
+        ]);
+        assert_eq!(
+            Ok(Heightmap {
+                heights: heightmap,
+                starts: vec![(0, 0)],
+                ends: vec![(5, 2)],
+            }),
+            parse_heightmap(INPUT)
+        );
+    }
+
+    #[test]
+    fn parse_heightmap_errors() {
+        assert_eq!(
+            Err(ParseError::Grid(aoc_grid::ParseError::InvalidChar {
+                pos: (2, 1),
+                c: '?'
+            })),
+            parse_heightmap(
+                "Sab
+ab?
+abE"
+            )
+        );
+        assert_eq!(Err(ParseError::MissingStart), parse_heightmap("abE"));
+        assert_eq!(Err(ParseError::MissingEnd), parse_heightmap("Sab"));
+        assert_eq!(Err(ParseError::MissingStart), parse_heightmap(""));
+    }
+
+    #[test]
+    fn nearest_pair_works() {
+        let heights = Grid::new(10, 1, 0);
+        assert_eq!(
+            Some(Nearest {
+                source: (4, 0),
+                target: (6, 0),
+                steps: 2
+            }),
+            nearest_pair(
+                &heights,
+                &[(0, 0), (4, 0)],
+                &[(9, 0), (6, 0)],
+                ClimbRule::PUZZLE
+            )
+        );
+        assert_eq!(
+            None,
+            nearest_pair(&heights, &[(0, 0)], &[], ClimbRule::PUZZLE)
+        );
+    }
+
+    #[test]
+    fn multiple_markers_work() {
+        let heightmap =
+            parse_heightmap("SSbcdefghijklmnopqrstuvwxyzE\nEaaaaaaaaaaaaaaaaaaaaaaaaaaa").unwrap();
+        assert_eq!(vec![(0, 0), (1, 0)], heightmap.starts);
+        assert_eq!(vec![(27, 0), (0, 1)], heightmap.ends);
+        // the second `E` is surrounded by low ground, so nothing can climb onto it
+        assert_eq!(Some(26), part1(&heightmap));
+        assert_eq!(
+            Some((1, 0)),
+            nearest_pair(
+                &heightmap.heights,
+                &heightmap.starts,
+                &heightmap.ends,
+                ClimbRule::PUZZLE
+            )
+            .map(|nearest| nearest.source)
+        );
+    }
+
+    #[test]
+    fn path_works() {
+        let map = parse_heightmap(INPUT).unwrap();
+        let (heightmap, start, end) = (map.heights, map.starts[0], map.ends[0]);
+        let bfs = Bfs::new(&heightmap, start, ClimbRule::PUZZLE);
+        let path = bfs.path(end).unwrap();
+        assert_eq!(32, path.len());
+        assert_eq!((start, end), (path[0], path[31]));
+        assert!(path
+            .windows(2)
+            .all(|step| heightmap.neighbours4(step[0]).any(|pos| pos == step[1])));
+
+        // without ever climbing, `E` is out of reach
+        let rule = ClimbRule {
+            max_ascent: Some(0),
+            ..ClimbRule::PUZZLE
+        };
+        let bfs = Bfs::new(&heightmap, start, rule);
+        assert_eq!(None, bfs.path(end));
+    }
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(Some(31), part1(&parse_heightmap(INPUT).unwrap()));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(Some(29), part2(&parse_heightmap(INPUT).unwrap()));
+    }
+
+    /// Heightmaps with `S` in a corner and a snake of cells winding back and
+    /// forth along the even rows, climbing by 0 or 1 at each cell. The odd
+    /// rows are random, apart from the cell joining the snake up, and now and
+    /// then a snake cell drops to a low letter. `E` goes where the snake
+    /// reaches `z`, or at its end if it never does, so some heightmaps have a
+    /// route and some don't.
+    fn heightmap(rng: &mut Rng) -> String {
+        let (width, height) = (rng.range(4..12), rng.range(5..12));
+        let cells = (0..width * height)
+            .map(|_| (rng.range(0..4u8), rng.range(0..200u8)))
+            .collect::<Vec<_>>();
+        let mut rows = (0..height)
+            .map(|y| {
+                let row = &cells[y * width..(y + 1) * width];
+                row.iter().map(|&(_, noise)| b'a' + noise % 26).collect()
+            })
+            .collect::<Vec<Vec<u8>>>();
+        let mut snake = vec![];
+        for y in (0..height).step_by(2) {
+            let mut xs = (0..width).collect::<Vec<_>>();
+            if y % 4 == 2 {
+                xs.reverse();
+            }
+            snake.extend(xs.iter().map(|&x| (x, y)));
+            if y + 2 < height {
+                snake.push((xs[width - 1], y + 1));
+            }
+        }
+
+        let mut level = 0;
+        let mut end = *snake.last().unwrap();
+        for &(x, y) in &snake[1..] {
+            let (climb, noise) = cells[y * width + x];
+            level += (climb > 0) as u8;
+            if level == 25 {
+                end = (x, y);
+                break;
+            }
+            rows[y][x] = if noise < 4 {
+                b'a' + noise
+            } else {
+                b'a' + level
+            };
+        }
+        rows[0][0] = b'S';
+        rows[end.1][end.0] = b'E';
+        let rows = rows.into_iter().map(|row| String::from_utf8(row).unwrap());
+        rows.collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn matches_naive() {
+        check(|rng| {
+            let input = heightmap(rng);
+            let heightmap = parse_heightmap(&input).unwrap();
+            assert_eq!(naive::part1(&input), part1(&heightmap));
+            assert_eq!(naive::part2(&input), part2(&heightmap));
+        });
+    }
+}
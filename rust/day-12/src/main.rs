@@ -1,143 +1,97 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process;
 
-fn main() {
-    let input = std::fs::read_to_string("input.txt").unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-}
+use aoc_args::Args;
+use day_12::{parse_heightmap, render, Bfs, ClimbRule, Heightmap, Nearest};
 
-fn parse_heightmap(input: &str) -> (Vec<Vec<i32>>, (usize, usize), (usize, usize)) {
-    let mut start = None;
-    let mut end = None;
-    let heightmap = input
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.bytes()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    b'a'..=b'z' => c - b'a',
-                    b'S' => {
-                        start = Some((x, y));
-                        0
-                    }
-                    b'E' => {
-                        end = Some((x, y));
-                        b'z' - b'a'
-                    }
-                    _ => unreachable!(),
-                } as i32)
-                .collect::<Vec<_>>()
-        })
-        .collect();
-    (heightmap, start.unwrap(), end.unwrap())
-}
+const USAGE: &str = "Usage: day-12 [--arrows] [--ansi] [--image FILE] [--distances FILE]
 
-type Pos = (usize, usize);
+With no options, prints the answers to both parts.
 
-fn bfs(heightmap: &Vec<Vec<i32>>, start: Pos, end: Pos) -> Option<usize> {
-    let height = heightmap.len();
-    let width = heightmap[0].len();
-    let mut queue = VecDeque::<_>::from([vec![start]]);
-    let mut visited = BTreeSet::new();
+  --arrows          print the part 1 route as arrows, like the puzzle text
+  --ansi            print the heightmap coloured by elevation with the route,
+                    then the part 2 distance-from-E field
+  --image FILE      write the heightmap and route as a PNG or PPM image
+  --distances FILE  write the part 2 distance-from-E field as a PNG or PPM image";
 
-    while let Some(path) = queue.pop_front() {
-        let pos = path[path.len() - 1];
-        if pos == end {
-            return Some(path.len() - 1);
-        }
-        if visited.contains(&pos) {
-            continue;
-        }
+const SCALE: usize = 6;
 
-        let start_y = if pos.1 > 0 { pos.1 - 1 } else { pos.1 };
-        let end_y = if pos.1 < height - 1 { pos.1 + 1 } else { pos.1 };
-        for y in start_y..=end_y {
-            let new_pos = (pos.0, y);
-            if new_pos == pos
-                || visited.contains(&new_pos)
-                || heightmap[new_pos.1][new_pos.0] - heightmap[pos.1][pos.0] > 1
-            {
-                continue;
-            }
-            let mut new_path = path.clone();
-            new_path.push(new_pos);
-            queue.push_back(new_path);
-        }
+#[derive(Default)]
+struct Options {
+    arrows: bool,
+    ansi: bool,
+    image: Option<PathBuf>,
+    distances: Option<PathBuf>,
+}
 
-        let start_x = if pos.0 > 0 { pos.0 - 1 } else { pos.0 };
-        let end_x = if pos.0 < width - 1 { pos.0 + 1 } else { pos.0 };
-        for x in start_x..=end_x {
-            let new_pos = (x, pos.1);
-            if new_pos == pos
-                || visited.contains(&new_pos)
-                || heightmap[new_pos.1][new_pos.0] - heightmap[pos.1][pos.0] > 1
-            {
-                continue;
-            }
-            let mut new_path = path.clone();
-            new_path.push(new_pos);
-            queue.push_back(new_path);
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--arrows" => options.arrows = true,
+            "--ansi" => options.ansi = true,
+            "--image" => options.image = Some(args.value(arg)?.into()),
+            "--distances" => options.distances = Some(args.value(arg)?.into()),
+            _ => return Err(aoc_args::unknown(arg)),
         }
-        visited.insert(pos);
     }
-    return None;
+    Ok(options)
 }
 
-fn part1(input: &str) -> usize {
-    let (heightmap, start, end) = parse_heightmap(input);
-    bfs(&heightmap, start, end).unwrap()
-}
+fn main() {
+    let args = aoc_args::from_env();
+    let options = parse_args(&args).unwrap_or_else(|err| aoc_args::usage_error(err, USAGE));
 
-fn part2(input: &str) -> usize {
-    let (heightmap, _, end) = parse_heightmap(input);
-    let mut steps = vec![];
+    let input = std::fs::read_to_string("input.txt").unwrap();
+    let heightmap = parse_heightmap(&input).unwrap_or_else(|err| {
+        eprintln!("input.txt: {}", err);
+        process::exit(1);
+    });
 
-    for (y, row) in heightmap.iter().enumerate() {
-        for (x, h) in row.iter().enumerate() {
-            if *h == 0 {
-                let start = (x, y);
-                if let Some(step_count) = bfs(&heightmap, start, end) {
-                    steps.push(step_count);
-                }
-            }
-        }
-    }
-    steps.sort();
-    steps[0]
-}
+    let Heightmap {
+        heights,
+        starts,
+        ends,
+    } = &heightmap;
+    // one search from the starts and one backwards from the ends give both
+    // answers, the part 1 route and the part 2 distance field
+    let from_start = Bfs::from_sources(heights, starts, ClimbRule::PUZZLE);
+    let from_end = Bfs::from_sources(heights, ends, ClimbRule::PUZZLE.reversed());
+    let part1 = from_start.nearest(ends);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    if args.is_empty() {
+        let answer = |nearest: Option<Nearest>| match nearest {
+            Some(nearest) => nearest.steps.to_string(),
+            None => "no route to E".to_string(),
+        };
+        println!("Part 1: {}", answer(part1));
+        println!("Part 2: {}", answer(from_end.nearest(&heightmap.lowest())));
+        return;
+    }
 
-    const INPUT: &str = "Sabqponm
-abcryxxl
-accszExk
-acctuvwj
-abdefghi";
+    let path = part1
+        .and_then(|nearest| from_start.path(nearest.target))
+        .unwrap_or_default();
 
-    #[test]
-    fn parse_heightmap_works() {
-        let heightmap = vec![
-            vec![0, 0, 1, 16, 15, 14, 13, 12],
-            vec![0, 1, 2, 17, 24, 23, 23, 11],
-            vec![0, 2, 2, 18, 25, 25, 23, 10],
-            vec![0, 2, 2, 19, 20, 21, 22, 9],
-            vec![0, 1, 3, 4, 5, 6, 7, 8],
-        ];
-        let start = (0, 0);
-        let end = (5, 2);
-        assert_eq!((heightmap, start, end), parse_heightmap(&INPUT));
+    if options.arrows {
+        println!("{}", render::draw_path(heights, &path));
     }
-
-    #[test]
-    fn part1_works() {
-        assert_eq!(31, part1(INPUT));
+    if options.ansi {
+        println!("{}", render::elevation_ansi(heights, &path));
+        println!("{}", render::distance_ansi(from_end.distances()));
+    }
+    if let Some(file) = &options.image {
+        save(&render::elevation_image(heights, &path, SCALE), file);
     }
+    if let Some(file) = &options.distances {
+        save(&render::distance_image(from_end.distances(), SCALE), file);
+    }
+}
 
-    #[test]
-    fn part2_works() {
-        assert_eq!(29, part2(INPUT));
+fn save(image: &render::Image, path: &Path) {
+    if let Err(err) = image.save(path) {
+        eprintln!("{}: {}", path.display(), err);
+        process::exit(1);
     }
 }
@@ -0,0 +1,103 @@
+//! A BFS that clones its path at every step, once per lowest cell in part 2.
+
+use std::collections::{BTreeSet, VecDeque};
+
+type Pos = (usize, usize);
+
+fn parse_heightmap(input: &str) -> (Vec<Vec<i32>>, Pos, Pos) {
+    let mut start = None;
+    let mut end = None;
+    let heightmap = input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.bytes()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    b'a'..=b'z' => c - b'a',
+                    b'S' => {
+                        start = Some((x, y));
+                        0
+                    }
+                    b'E' => {
+                        end = Some((x, y));
+                        b'z' - b'a'
+                    }
+                    _ => unreachable!(),
+                } as i32)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (heightmap, start.unwrap(), end.unwrap())
+}
+
+fn bfs(heightmap: &[Vec<i32>], start: Pos, end: Pos) -> Option<usize> {
+    let height = heightmap.len();
+    let width = heightmap[0].len();
+    let mut queue = VecDeque::<_>::from([vec![start]]);
+    let mut visited = BTreeSet::new();
+
+    while let Some(path) = queue.pop_front() {
+        let pos = path[path.len() - 1];
+        if pos == end {
+            return Some(path.len() - 1);
+        }
+        if visited.contains(&pos) {
+            continue;
+        }
+
+        let start_y = if pos.1 > 0 { pos.1 - 1 } else { pos.1 };
+        let end_y = if pos.1 < height - 1 { pos.1 + 1 } else { pos.1 };
+        for y in start_y..=end_y {
+            let new_pos = (pos.0, y);
+            if new_pos == pos
+                || visited.contains(&new_pos)
+                || heightmap[new_pos.1][new_pos.0] - heightmap[pos.1][pos.0] > 1
+            {
+                continue;
+            }
+            let mut new_path = path.clone();
+            new_path.push(new_pos);
+            queue.push_back(new_path);
+        }
+
+        let start_x = if pos.0 > 0 { pos.0 - 1 } else { pos.0 };
+        let end_x = if pos.0 < width - 1 { pos.0 + 1 } else { pos.0 };
+        for x in start_x..=end_x {
+            let new_pos = (x, pos.1);
+            if new_pos == pos
+                || visited.contains(&new_pos)
+                || heightmap[new_pos.1][new_pos.0] - heightmap[pos.1][pos.0] > 1
+            {
+                continue;
+            }
+            let mut new_path = path.clone();
+            new_path.push(new_pos);
+            queue.push_back(new_path);
+        }
+        visited.insert(pos);
+    }
+    None
+}
+
+pub fn part1(input: &str) -> Option<usize> {
+    let (heightmap, start, end) = parse_heightmap(input);
+    bfs(&heightmap, start, end)
+}
+
+pub fn part2(input: &str) -> Option<usize> {
+    let (heightmap, _, end) = parse_heightmap(input);
+    let mut steps = vec![];
+
+    for (y, row) in heightmap.iter().enumerate() {
+        for (x, h) in row.iter().enumerate() {
+            if *h == 0 {
+                let start = (x, y);
+                if let Some(step_count) = bfs(&heightmap, start, end) {
+                    steps.push(step_count);
+                }
+            }
+        }
+    }
+    steps.into_iter().min()
+}
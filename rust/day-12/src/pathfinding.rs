@@ -0,0 +1,252 @@
+//! Weighted searches over the heightmap, for when every step doesn't cost the
+//! same. Unit-cost searches are better served by [`Bfs`](crate::Bfs).
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use aoc_grid::{Direction, Grid, Point, Pos};
+
+/// Which steps between neighbouring cells are allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClimbRule {
+    /// How much higher the next cell may be, or `None` for no limit.
+    pub max_ascent: Option<i32>,
+    /// How much lower the next cell may be, or `None` for no limit.
+    pub max_descent: Option<i32>,
+    /// Whether diagonal neighbours can be stepped to.
+    pub diagonal: bool,
+}
+
+impl ClimbRule {
+    /// The puzzle's rule: at most one higher, any amount lower, no diagonals.
+    pub const PUZZLE: ClimbRule = ClimbRule {
+        max_ascent: Some(1),
+        max_descent: None,
+        diagonal: false,
+    };
+
+    pub fn allows(&self, from: i32, to: i32) -> bool {
+        let climb = to - from;
+        self.max_ascent.is_none_or(|max| climb <= max)
+            && self.max_descent.is_none_or(|max| -climb <= max)
+    }
+
+    /// The same rule for walking a route backwards, from its end to its start.
+    pub fn reversed(self) -> ClimbRule {
+        ClimbRule {
+            max_ascent: self.max_descent,
+            max_descent: self.max_ascent,
+            diagonal: self.diagonal,
+        }
+    }
+
+    /// Neighbours of `pos` that can be stepped to under this rule.
+    pub fn moves<'a>(
+        &'a self,
+        heightmap: &'a Grid<i32>,
+        pos: Pos,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        let dirs: &[Direction] = if self.diagonal {
+            &Direction::ALL
+        } else {
+            &Direction::CARDINAL
+        };
+        dirs.iter()
+            .filter_map(move |&dir| heightmap.step(pos, dir))
+            .filter(move |&next| self.allows(heightmap[pos], heightmap[next]))
+    }
+}
+
+impl Default for ClimbRule {
+    fn default() -> Self {
+        ClimbRule::PUZZLE
+    }
+}
+
+/// Every step costs the same.
+pub fn unit_cost(_from: i32, _to: i32) -> u64 {
+    1
+}
+
+/// Every step costs one, plus `penalty` for each unit of height climbed.
+pub fn climb_penalty(penalty: u64) -> impl Fn(i32, i32) -> u64 {
+    move |from, to| 1 + penalty * (to - from).max(0) as u64
+}
+
+/// A cheapest route and what it costs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Route {
+    pub cost: u64,
+    /// Every cell from the start to the goal, including both.
+    pub path: Vec<Pos>,
+}
+
+/// Dijkstra's algorithm. `cost(from, to)` gets the heights of the two cells of
+/// each step.
+pub fn dijkstra<F>(
+    heightmap: &Grid<i32>,
+    start: Pos,
+    goal: Pos,
+    rule: ClimbRule,
+    cost: F,
+) -> Option<Route>
+where
+    F: Fn(i32, i32) -> u64,
+{
+    search(heightmap, start, goal, rule, cost, |_| 0)
+}
+
+/// A* with a Manhattan distance heuristic, or Chebyshev distance when
+/// diagonal moves are allowed. The route is only guaranteed to be cheapest if
+/// every step costs at least one.
+pub fn astar<F>(
+    heightmap: &Grid<i32>,
+    start: Pos,
+    goal: Pos,
+    rule: ClimbRule,
+    cost: F,
+) -> Option<Route>
+where
+    F: Fn(i32, i32) -> u64,
+{
+    let goal_point = Point::from(goal);
+    let heuristic = |pos: Pos| {
+        let point = Point::from(pos);
+        let distance = if rule.diagonal {
+            point.chebyshev(goal_point)
+        } else {
+            point.manhattan(goal_point)
+        };
+        distance as u64
+    };
+    search(heightmap, start, goal, rule, cost, heuristic)
+}
+
+fn search<F, H>(
+    heightmap: &Grid<i32>,
+    start: Pos,
+    goal: Pos,
+    rule: ClimbRule,
+    cost: F,
+    heuristic: H,
+) -> Option<Route>
+where
+    F: Fn(i32, i32) -> u64,
+    H: Fn(Pos) -> u64,
+{
+    heightmap.get(start)?;
+    heightmap.get(goal)?;
+
+    let mut costs = Grid::new(heightmap.width(), heightmap.height(), u64::MAX);
+    let mut parents = Grid::new(heightmap.width(), heightmap.height(), None);
+    // ordered by estimated total cost, then by cost so far
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+    costs[start] = 0;
+
+    while let Some(Reverse((_, cost_so_far, pos))) = queue.pop() {
+        if pos == goal {
+            return Some(Route {
+                cost: cost_so_far,
+                path: walk_back(&parents, goal),
+            });
+        }
+        if cost_so_far > costs[pos] {
+            // a cheaper way here has already been expanded
+            continue;
+        }
+        for next in rule.moves(heightmap, pos) {
+            let next_cost = cost_so_far + cost(heightmap[pos], heightmap[next]);
+            if next_cost < costs[next] {
+                costs[next] = next_cost;
+                parents[next] = Some(pos);
+                queue.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+fn walk_back(parents: &Grid<Option<Pos>>, goal: Pos) -> Vec<Pos> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents[path[path.len() - 1]] {
+        path.push(parent);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_heightmap;
+    use crate::tests::INPUT;
+
+    #[test]
+    fn climb_rule_works() {
+        let rule = ClimbRule::PUZZLE;
+        assert!(rule.allows(3, 4));
+        assert!(!rule.allows(3, 5));
+        assert!(rule.allows(25, 0));
+
+        let reversed = rule.reversed();
+        assert!(reversed.allows(4, 3));
+        assert!(!reversed.allows(5, 3));
+        assert!(reversed.allows(0, 25));
+    }
+
+    #[test]
+    fn dijkstra_and_astar_match_bfs() {
+        let map = parse_heightmap(INPUT).unwrap();
+        let (heightmap, start, end) = (map.heights, map.starts[0], map.ends[0]);
+        for search in [dijkstra, astar] {
+            let route = search(&heightmap, start, end, ClimbRule::PUZZLE, unit_cost).unwrap();
+            assert_eq!(31, route.cost);
+            assert_eq!(32, route.path.len());
+            assert_eq!((start, end), (route.path[0], route.path[31]));
+        }
+    }
+
+    #[test]
+    fn climb_penalty_works() {
+        // going around the hill is longer but avoids the climb
+        let heightmap = Grid::from_vec(3, vec![0, 5, 0, 0, 0, 0]);
+        let rule = ClimbRule {
+            max_ascent: None,
+            ..ClimbRule::PUZZLE
+        };
+        let route = dijkstra(&heightmap, (0, 0), (2, 0), rule, unit_cost).unwrap();
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0)], route.path);
+        let route = dijkstra(&heightmap, (0, 0), (2, 0), rule, climb_penalty(10)).unwrap();
+        assert_eq!(vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)], route.path);
+        assert_eq!(4, route.cost);
+    }
+
+    #[test]
+    fn diagonal_moves_work() {
+        let heightmap = Grid::new(4, 4, 0);
+        let rule = ClimbRule {
+            diagonal: true,
+            ..ClimbRule::PUZZLE
+        };
+        let route = astar(&heightmap, (0, 0), (3, 3), rule, unit_cost).unwrap();
+        assert_eq!(3, route.cost);
+        let route = astar(&heightmap, (0, 0), (3, 3), ClimbRule::PUZZLE, unit_cost).unwrap();
+        assert_eq!(6, route.cost);
+    }
+
+    #[test]
+    fn unreachable_goal_is_none() {
+        let map = parse_heightmap(INPUT).unwrap();
+        let (heightmap, start, end) = (map.heights, map.starts[0], map.ends[0]);
+        let rule = ClimbRule {
+            max_ascent: Some(0),
+            ..ClimbRule::PUZZLE
+        };
+        assert_eq!(None, dijkstra(&heightmap, start, end, rule, unit_cost));
+        assert_eq!(None, astar(&heightmap, start, end, rule, unit_cost));
+        assert_eq!(
+            None,
+            astar(&heightmap, start, (99, 99), ClimbRule::PUZZLE, unit_cost)
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Pictures of the heightmap, the route through it and the distance field.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use aoc_grid::{Grid, Pos};
+
+type Rgb = [u8; 3];
+
+const PATH_COLOUR: Rgb = [230, 30, 30];
+const UNREACHABLE_COLOUR: Rgb = [0, 0, 0];
+
+/// Draws `path` the way the puzzle does: each cell points at the next one
+/// with `>`, `<`, `^` or `v`, the last cell is `E` and the rest is `.`.
+pub fn draw_path(heightmap: &Grid<i32>, path: &[Pos]) -> Grid<char> {
+    let mut drawing = Grid::new(heightmap.width(), heightmap.height(), '.');
+    for step in path.windows(2) {
+        let ((x0, y0), (x1, y1)) = (step[0], step[1]);
+        drawing[step[0]] = if x1 > x0 {
+            '>'
+        } else if x1 < x0 {
+            '<'
+        } else if y1 > y0 {
+            'v'
+        } else {
+            '^'
+        };
+    }
+    if let Some(&end) = path.last() {
+        drawing[end] = 'E';
+    }
+    drawing
+}
+
+fn lerp(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    [
+        mix(from[0], to[0]),
+        mix(from[1], to[1]),
+        mix(from[2], to[2]),
+    ]
+}
+
+/// Picks a colour along a gradient through `stops` for `t` in `0.0..=1.0`.
+fn gradient(stops: &[Rgb], t: f64) -> Rgb {
+    let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let idx = (scaled as usize).min(stops.len() - 2);
+    lerp(stops[idx], stops[idx + 1], scaled - idx as f64)
+}
+
+/// Valleys are green, slopes brown and the peaks white.
+fn elevation_colour(height: i32) -> Rgb {
+    gradient(
+        &[[30, 90, 40], [200, 170, 90], [250, 250, 250]],
+        height as f64 / 25.0,
+    )
+}
+
+/// Near cells are blue, far cells red.
+fn distance_colour(distance: usize, max: usize) -> Rgb {
+    gradient(
+        &[[40, 60, 200], [240, 220, 60], [220, 40, 40]],
+        distance as f64 / max.max(1) as f64,
+    )
+}
+
+fn ansi_background([r, g, b]: Rgb) -> String {
+    format!("\x1b[48;2;{};{};{}m", r, g, b)
+}
+
+const ANSI_PATH: &str = "\x1b[1;38;2;230;30;30m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// The heightmap as letters on a background coloured by elevation, with
+/// `path` overlaid as bold arrows.
+pub fn elevation_ansi(heightmap: &Grid<i32>, path: &[Pos]) -> String {
+    let arrows = draw_path(heightmap, path);
+    let mut out = String::new();
+    for y in 0..heightmap.height() {
+        for x in 0..heightmap.width() {
+            let height = heightmap[(x, y)];
+            out.push_str(&ansi_background(elevation_colour(height)));
+            match arrows[(x, y)] {
+                '.' => out.push((b'a' + height as u8) as char),
+                arrow => {
+                    out.push_str(ANSI_PATH);
+                    out.push(arrow);
+                }
+            }
+            out.push_str(ANSI_RESET);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn max_distance(distances: &Grid<Option<usize>>) -> usize {
+    distances
+        .iter()
+        .filter_map(|(_, distance)| *distance)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The distance field as coloured blocks, with unreachable cells left black.
+pub fn distance_ansi(distances: &Grid<Option<usize>>) -> String {
+    let max = max_distance(distances);
+    let mut out = String::new();
+    for y in 0..distances.height() {
+        for distance in distances.row(y) {
+            let colour = match distance {
+                Some(distance) => distance_colour(*distance, max),
+                None => UNREACHABLE_COLOUR,
+            };
+            out.push_str(&ansi_background(colour));
+            out.push_str("  ");
+        }
+        out.push_str(ANSI_RESET);
+        out.push('\n');
+    }
+    out
+}
+
+/// An RGB picture with each grid cell drawn as a `scale` pixel square.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn from_cells(cells: &Grid<Rgb>, scale: usize) -> Image {
+        let mut pixels = Vec::with_capacity(cells.width() * cells.height() * scale * scale * 3);
+        for y in 0..cells.height() {
+            let line = cells
+                .row(y)
+                .flat_map(|colour| colour.repeat(scale))
+                .collect::<Vec<u8>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Image {
+            width: cells.width() * scale,
+            height: cells.height() * scale,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The colour of the pixel at `(x, y)`.
+    pub fn pixel(&self, (x, y): Pos) -> Rgb {
+        let idx = (y * self.width + x) * 3;
+        [self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2]]
+    }
+
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "P6\n{} {}\n255", self.width, self.height)?;
+        out.write_all(&self.pixels)
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(writer.finish()?)
+    }
+
+    /// Writes a PNG if `path` ends in `.png` and a PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|ext| ext == "png") {
+            self.write_png(&mut out)?;
+        } else {
+            self.write_ppm(&mut out)?;
+        }
+        out.flush()
+    }
+}
+
+/// The heightmap coloured by elevation with `path` overlaid in red.
+pub fn elevation_image(heightmap: &Grid<i32>, path: &[Pos], scale: usize) -> Image {
+    let mut cells = heightmap.map(|&height| elevation_colour(height));
+    for &pos in path {
+        cells[pos] = PATH_COLOUR;
+    }
+    Image::from_cells(&cells, scale)
+}
+
+/// The distance field coloured from near to far, with unreachable cells black.
+pub fn distance_image(distances: &Grid<Option<usize>>, scale: usize) -> Image {
+    let max = max_distance(distances);
+    let cells = distances.map(|distance| match distance {
+        Some(distance) => distance_colour(*distance, max),
+        None => UNREACHABLE_COLOUR,
+    });
+    Image::from_cells(&cells, scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+    use crate::{parse_heightmap, Bfs, ClimbRule};
+
+    #[test]
+    fn draw_path_matches_puzzle() {
+        let map = parse_heightmap(INPUT).unwrap();
+        let (heightmap, start, end) = (map.heights, map.starts[0], map.ends[0]);
+        let path = Bfs::new(&heightmap, start, ClimbRule::PUZZLE)
+            .path(end)
+            .unwrap();
+        // the puzzle draws one of several shortest paths, so only compare
+        // the parts every shortest path shares
+        let drawing = draw_path(&heightmap, &path).to_string();
+        assert_eq!(31, drawing.chars().filter(|c| "<>^v".contains(*c)).count());
+        assert_eq!("..>>>>>^", drawing.lines().last().unwrap());
+        assert_eq!(Some('E'), draw_path(&heightmap, &path).get(end).copied());
+    }
+
+    #[test]
+    fn draw_path_arrows() {
+        let heightmap = Grid::new(3, 2, 0);
+        let path = [(0, 0), (1, 0), (1, 1), (0, 1)];
+        assert_eq!(">v.\nE<.", draw_path(&heightmap, &path).to_string());
+    }
+
+    #[test]
+    fn gradient_works() {
+        assert_eq!([30, 90, 40], elevation_colour(0));
+        assert_eq!([250, 250, 250], elevation_colour(25));
+        assert_eq!([40, 60, 200], distance_colour(0, 10));
+        assert_eq!([220, 40, 40], distance_colour(10, 10));
+    }
+
+    #[test]
+    fn elevation_image_works() {
+        let heightmap = Grid::from_vec(2, vec![0, 25]);
+        let image = elevation_image(&heightmap, &[(1, 0)], 3);
+        assert_eq!((6, 3), (image.width(), image.height()));
+        assert_eq!(elevation_colour(0), image.pixel((2, 2)));
+        assert_eq!(PATH_COLOUR, image.pixel((3, 0)));
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n6 3\n255\n"));
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn distance_image_works() {
+        let distances = Grid::from_vec(3, vec![Some(0), Some(4), None]);
+        let image = distance_image(&distances, 1);
+        assert_eq!(distance_colour(0, 4), image.pixel((0, 0)));
+        assert_eq!(distance_colour(4, 4), image.pixel((1, 0)));
+        assert_eq!(UNREACHABLE_COLOUR, image.pixel((2, 0)));
+    }
+}
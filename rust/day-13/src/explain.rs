@@ -0,0 +1,304 @@
+//! Step-by-step comparisons, written the way the puzzle walks through its
+//! example pairs.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::Item;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// How a comparison was decided.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    LeftSmaller,
+    RightSmaller,
+    LeftRanOut,
+    RightRanOut,
+}
+
+impl Verdict {
+    /// Whether the verdict puts the pair in the right order.
+    pub fn in_order(self) -> bool {
+        matches!(self, Verdict::LeftSmaller | Verdict::LeftRanOut)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            Verdict::LeftSmaller => "Left side is smaller",
+            Verdict::RightSmaller => "Right side is smaller",
+            Verdict::LeftRanOut => "Left side ran out of items",
+            Verdict::RightRanOut => "Right side ran out of items",
+        };
+        let order = if self.in_order() { "" } else { "not " };
+        write!(f, "{}, so inputs are {}in the right order", reason, order)
+    }
+}
+
+/// One line of a trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Comparing two items, and the steps that took.
+    Compare {
+        left: Item,
+        right: Item,
+        steps: Vec<Step>,
+    },
+    /// A number on `side` was wrapped in a list to compare it with a list.
+    Convert {
+        side: Side,
+        to: Item,
+    },
+    Verdict(Verdict),
+}
+
+impl Step {
+    fn write(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}- ", "", indent = depth * 2)?;
+        match self {
+            Step::Compare { left, right, steps } => {
+                writeln!(f, "Compare {} vs {}", left, right)?;
+                for step in steps {
+                    step.write(f, depth + 1)?;
+                }
+                Ok(())
+            }
+            Step::Convert { side, to } => {
+                let side = match side {
+                    Side::Left => "left",
+                    Side::Right => "right",
+                };
+                writeln!(
+                    f,
+                    "Mixed types; convert {} to {} and retry comparison",
+                    side, to
+                )
+            }
+            Step::Verdict(verdict) => writeln!(f, "{}", verdict),
+        }
+    }
+}
+
+/// The outcome of [`compare_explained`]. Displays as the puzzle's indented
+/// list of steps, one per line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    pub ordering: Ordering,
+    /// Always a [`Step::Compare`] of the two packets.
+    pub root: Step,
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.root.write(f, 0)
+    }
+}
+
+/// Compares two items like [`Ord`] does, recording every step on the way.
+pub fn compare_explained(left: &Item, right: &Item) -> Trace {
+    let (root, ordering) = compare(left, right);
+    Trace { ordering, root }
+}
+
+fn compare(left: &Item, right: &Item) -> (Step, Ordering) {
+    let mut steps = Vec::new();
+    let ordering = match (left, right) {
+        (Item::Num(left_num), Item::Num(right_num)) => {
+            let ordering = left_num.cmp(right_num);
+            push_verdict(
+                &mut steps,
+                ordering,
+                Verdict::LeftSmaller,
+                Verdict::RightSmaller,
+            );
+            ordering
+        }
+        (Item::List(left_list), Item::List(right_list)) => {
+            let mut ordering = Ordering::Equal;
+            for (left_item, right_item) in left_list.iter().zip(right_list) {
+                let (step, item_ordering) = compare(left_item, right_item);
+                steps.push(step);
+                ordering = item_ordering;
+                if ordering.is_ne() {
+                    break;
+                }
+            }
+            if ordering.is_eq() {
+                ordering = left_list.len().cmp(&right_list.len());
+                push_verdict(
+                    &mut steps,
+                    ordering,
+                    Verdict::LeftRanOut,
+                    Verdict::RightRanOut,
+                );
+            }
+            ordering
+        }
+        (Item::Num(num), Item::List(_)) => {
+            let to = Item::List(vec![Item::Num(*num)]);
+            let (step, ordering) = compare(&to, right);
+            steps.push(Step::Convert {
+                side: Side::Left,
+                to,
+            });
+            steps.push(step);
+            ordering
+        }
+        (Item::List(_), Item::Num(num)) => {
+            let to = Item::List(vec![Item::Num(*num)]);
+            let (step, ordering) = compare(left, &to);
+            steps.push(Step::Convert {
+                side: Side::Right,
+                to,
+            });
+            steps.push(step);
+            ordering
+        }
+    };
+    let step = Step::Compare {
+        left: left.clone(),
+        right: right.clone(),
+        steps,
+    };
+    (step, ordering)
+}
+
+fn push_verdict(steps: &mut Vec<Step>, ordering: Ordering, less: Verdict, greater: Verdict) {
+    match ordering {
+        Ordering::Less => steps.push(Step::Verdict(less)),
+        Ordering::Greater => steps.push(Step::Verdict(greater)),
+        Ordering::Equal => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{packet, INPUT};
+    use crate::{explain_pairs, list};
+    use aoc_check::check;
+
+    /// The walkthrough from the puzzle text.
+    const EXPLAINED: &str = "== Pair 1 ==
+- Compare [1,1,3,1,1] vs [1,1,5,1,1]
+  - Compare 1 vs 1
+  - Compare 1 vs 1
+  - Compare 3 vs 5
+    - Left side is smaller, so inputs are in the right order
+
+== Pair 2 ==
+- Compare [[1],[2,3,4]] vs [[1],4]
+  - Compare [1] vs [1]
+    - Compare 1 vs 1
+  - Compare [2,3,4] vs 4
+    - Mixed types; convert right to [4] and retry comparison
+    - Compare [2,3,4] vs [4]
+      - Compare 2 vs 4
+        - Left side is smaller, so inputs are in the right order
+
+== Pair 3 ==
+- Compare [9] vs [[8,7,6]]
+  - Compare 9 vs [8,7,6]
+    - Mixed types; convert left to [9] and retry comparison
+    - Compare [9] vs [8,7,6]
+      - Compare 9 vs 8
+        - Right side is smaller, so inputs are not in the right order
+
+== Pair 4 ==
+- Compare [[4,4],4,4] vs [[4,4],4,4,4]
+  - Compare [4,4] vs [4,4]
+    - Compare 4 vs 4
+    - Compare 4 vs 4
+  - Compare 4 vs 4
+  - Compare 4 vs 4
+  - Left side ran out of items, so inputs are in the right order
+
+== Pair 5 ==
+- Compare [7,7,7,7] vs [7,7,7]
+  - Compare 7 vs 7
+  - Compare 7 vs 7
+  - Compare 7 vs 7
+  - Right side ran out of items, so inputs are not in the right order
+
+== Pair 6 ==
+- Compare [] vs [3]
+  - Left side ran out of items, so inputs are in the right order
+
+== Pair 7 ==
+- Compare [[[]]] vs [[]]
+  - Compare [[]] vs []
+    - Right side ran out of items, so inputs are not in the right order
+
+== Pair 8 ==
+- Compare [1,[2,[3,[4,[5,6,7]]]],8,9] vs [1,[2,[3,[4,[5,6,0]]]],8,9]
+  - Compare 1 vs 1
+  - Compare [2,[3,[4,[5,6,7]]]] vs [2,[3,[4,[5,6,0]]]]
+    - Compare 2 vs 2
+    - Compare [3,[4,[5,6,7]]] vs [3,[4,[5,6,0]]]
+      - Compare 3 vs 3
+      - Compare [4,[5,6,7]] vs [4,[5,6,0]]
+        - Compare 4 vs 4
+        - Compare [5,6,7] vs [5,6,0]
+          - Compare 5 vs 5
+          - Compare 6 vs 6
+          - Compare 7 vs 0
+            - Right side is smaller, so inputs are not in the right order
+";
+
+    #[test]
+    fn matches_puzzle_walkthrough() {
+        assert_eq!(EXPLAINED, explain_pairs(INPUT));
+    }
+
+    #[test]
+    fn trace_structure() {
+        let (_, left) = list("[9]").unwrap();
+        let (_, right) = list("[[8,7,6]]").unwrap();
+        let trace = compare_explained(&left, &right);
+        assert_eq!(Ordering::Greater, trace.ordering);
+        let Step::Compare { steps, .. } = &trace.root else {
+            panic!("root should be a comparison");
+        };
+        let Step::Compare { steps, .. } = &steps[0] else {
+            panic!("expected 9 vs [8,7,6]");
+        };
+        assert_eq!(
+            Step::Convert {
+                side: Side::Left,
+                to: Item::List(vec![Item::Num(9)])
+            },
+            steps[0]
+        );
+    }
+
+    #[test]
+    fn equal_items_have_no_verdict() {
+        let (_, packet) = list("[1,[2]]").unwrap();
+        let trace = compare_explained(&packet, &packet);
+        assert_eq!(Ordering::Equal, trace.ordering);
+        assert!(!trace.to_string().contains("order"));
+    }
+
+    #[test]
+    fn log_is_up_to_date() {
+        // regenerate with `cargo run -- --explain > log`
+        assert_eq!(
+            include_str!("../log"),
+            explain_pairs(include_str!("../input.txt"))
+        );
+    }
+
+    #[test]
+    fn ordering_matches_ord() {
+        check(|rng| {
+            let (left, right) = (packet(rng), packet(rng));
+            assert_eq!(left.cmp(&right), compare_explained(&left, &right).ordering);
+        });
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::Item;
+
+impl From<&Item> for Value {
+    fn from(item: &Item) -> Self {
+        match item {
+            Item::Num(num) => Value::from(*num),
+            Item::List(items) => Value::Array(items.iter().map(Value::from).collect()),
+        }
+    }
+}
+
+/// A JSON value that isn't a packet: anything other than arrays and
+/// integers between 0 and `u32::MAX`.
+#[derive(Debug, PartialEq)]
+pub struct FromJsonError(pub Value);
+
+impl fmt::Display for FromJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not a packet item", self.0)
+    }
+}
+
+impl std::error::Error for FromJsonError {}
+
+impl TryFrom<&Value> for Item {
+    type Error = FromJsonError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Number(num) => num
+                .as_u64()
+                .and_then(|num| u32::try_from(num).ok())
+                .map(Item::Num)
+                .ok_or_else(|| FromJsonError(value.clone())),
+            Value::Array(values) => values
+                .iter()
+                .map(Item::try_from)
+                .collect::<Result<_, _>>()
+                .map(Item::List),
+            _ => Err(FromJsonError(value.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list;
+    use crate::tests::packet;
+    use aoc_check::check;
+    use serde_json::json;
+
+    #[test]
+    fn to_json_works() {
+        let (_, packet) = list("[1,[2,[]],3]").unwrap();
+        assert_eq!(json!([1, [2, []], 3]), Value::from(&packet));
+    }
+
+    #[test]
+    fn from_json_works() {
+        let (_, packet) = list("[[4,4],4]").unwrap();
+        assert_eq!(Ok(packet), Item::try_from(&json!([[4, 4], 4])));
+        assert_eq!(
+            Err(FromJsonError(json!(-1))),
+            Item::try_from(&json!([1, [-1]]))
+        );
+        assert_eq!(
+            Err(FromJsonError(json!(1.5))),
+            Item::try_from(&json!([1.5]))
+        );
+        assert_eq!(
+            Err(FromJsonError(json!(4294967296u64))),
+            Item::try_from(&json!(4294967296u64))
+        );
+        assert_eq!(
+            Err(FromJsonError(json!({"a": 1}))),
+            Item::try_from(&json!({"a": 1}))
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        check(|rng| {
+            let packet = packet(rng);
+            let json = Value::from(&packet).to_string();
+            // serde_json prints arrays exactly like the puzzle does
+            assert_eq!(packet.to_string(), json);
+            let value = serde_json::from_str::<Value>(&json).unwrap();
+            assert_eq!(Ok(packet), Item::try_from(&value));
+        });
+    }
+}
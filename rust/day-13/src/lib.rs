@@ -0,0 +1,365 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::line_ending,
+    combinator::{cut, opt},
+    multi::{many0, many1, separated_list0},
+    sequence::{pair, preceded, separated_pair, terminated},
+    IResult,
+};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::{fmt, iter, slice};
+
+mod explain;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(test)]
+mod naive;
+mod reader;
+mod text;
+
+pub use explain::{compare_explained, Side, Step, Trace, Verdict};
+pub use reader::{
+    read_divider_positions, read_ordered_pair_sum, ErrorKind, PacketReader, Pairs, ParseError,
+    ReadError,
+};
+pub use text::{compare_text, TextError};
+
+#[cfg(feature = "json")]
+pub use json::FromJsonError;
+
+pub fn part1(input: &str) -> usize {
+    let (_, pairs) = pairs(input).unwrap();
+    pairs
+        .iter()
+        .enumerate()
+        .filter_map(
+            |(idx, (left, right))| {
+                if left <= right {
+                    Some(idx + 1)
+                } else {
+                    None
+                }
+            },
+        )
+        .sum()
+}
+
+/// Every pair's comparison in the puzzle's walkthrough format, numbered from 1
+/// and separated by blank lines.
+pub fn explain_pairs(input: &str) -> String {
+    let (_, pairs) = pairs(input).unwrap();
+    pairs
+        .iter()
+        .enumerate()
+        .map(|(idx, (left, right))| {
+            format!("== Pair {} ==\n{}", idx + 1, compare_explained(left, right))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The 1-based position each divider would have if it were added to
+/// `packets` and everything sorted, found by counting what sorts before it in
+/// a single pass over `packets`. Packets equal to a divider sort before it, and
+/// equal dividers keep their order.
+pub fn divider_positions<P>(packets: impl IntoIterator<Item = P>, dividers: &[Item]) -> Vec<usize>
+where
+    P: Borrow<Item>,
+{
+    let mut positions = dividers
+        .iter()
+        .enumerate()
+        .map(|(idx, divider)| {
+            let dividers_before = dividers
+                .iter()
+                .enumerate()
+                .filter(|&(other_idx, other)| other.cmp(divider).then(other_idx.cmp(&idx)).is_lt())
+                .count();
+            dividers_before + 1
+        })
+        .collect::<Vec<_>>();
+    for packet in packets {
+        for (position, divider) in positions.iter_mut().zip(dividers) {
+            if packet.borrow() <= divider {
+                *position += 1;
+            }
+        }
+    }
+    positions
+}
+
+/// The puzzle's divider packets, `[[2]]` and `[[6]]`.
+pub fn puzzle_dividers() -> [Item; 2] {
+    [2, 6].map(|num| Item::List(vec![Item::List(vec![Item::Num(num)])]))
+}
+
+pub fn part2(input: &str) -> usize {
+    let (_, packets) = packets(input).unwrap();
+    divider_positions(&packets, &puzzle_dividers())
+        .iter()
+        .product()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Item {
+    Num(u32),
+    List(Vec<Item>),
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Item::Num(self_num), Item::Num(other_num)) => self_num.cmp(other_num),
+            (Item::List(self_list), Item::List(other_list)) => self_list.cmp(other_list),
+            // a number compares as a list holding just that number
+            (Item::Num(_), Item::List(other_list)) => {
+                slice::from_ref(self).cmp(other_list.as_slice())
+            }
+            (Item::List(self_list), Item::Num(_)) => {
+                self_list.as_slice().cmp(slice::from_ref(other))
+            }
+        }
+    }
+}
+
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Prints the packet back in the puzzle's syntax, e.g. `[1,[2,3]]`.
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Item::Num(num) => write!(f, "{}", num),
+            Item::List(items) => {
+                write!(f, "[")?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn num(input: &str) -> IResult<&str, Item> {
+    let (input, num) = nom::character::complete::u32(input)?;
+    Ok((input, Item::Num(num)))
+}
+
+/// How deeply [`list`] lets lists nest, so a long run of `[` is an error
+/// rather than a stack overflow.
+pub const MAX_DEPTH: usize = 256;
+
+pub fn list(input: &str) -> IResult<&str, Item> {
+    nested_list(input, 1)
+}
+
+fn nested_list(input: &str, depth: usize) -> IResult<&str, Item> {
+    let (rest, _) = tag("[")(input)?;
+    if depth > MAX_DEPTH {
+        return Err(nom::Err::Failure(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::TooLarge,
+        )));
+    }
+    // once a list is open anything wrong inside it is a failure, not something
+    // to backtrack from, so errors are reported where they happened
+    let (input, items) = cut(terminated(|input| list_items(input, depth), tag("]")))(rest)?;
+    Ok((input, Item::List(items)))
+}
+
+fn list_items(input: &str, depth: usize) -> IResult<&str, Vec<Item>> {
+    let mut item = alt((num, |input| nested_list(input, depth + 1)));
+    let (input, first) = opt(&mut item)(input)?;
+    let Some(first) = first else {
+        return Ok((input, Vec::new()));
+    };
+    let (input, rest) = many0(preceded(tag(","), cut(item)))(input)?;
+    Ok((input, iter::once(first).chain(rest).collect()))
+}
+
+pub type Pair = (Item, Item);
+
+fn list_pair(input: &str) -> IResult<&str, Pair> {
+    let (input, lists) = separated_pair(list, line_ending, list)(input)?;
+    Ok((input, lists))
+}
+
+// Pairs of packets, separated by 2 line endings
+pub fn pairs(input: &str) -> IResult<&str, Vec<Pair>> {
+    separated_list0(pair(line_ending, line_ending), list_pair)(input)
+}
+
+/// List of packets ignoring line endings in between
+pub fn packets(input: &str) -> IResult<&str, Vec<Item>> {
+    separated_list0(many1(line_ending), list)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_check::{check, Rng};
+
+    pub(crate) const INPUT: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn part1_works() {
+        assert_eq!(13, part1(INPUT));
+    }
+
+    #[test]
+    fn part2_works() {
+        assert_eq!(140, part2(INPUT));
+    }
+
+    #[test]
+    fn list_works() {
+        assert_eq!(Ok(("", Item::List(vec![]))), list("[]"));
+        assert_eq!(Ok(("", Item::List(vec![Item::Num(3)]))), list("[3]"));
+        assert_eq!(
+            Ok(("", Item::List(vec![Item::Num(3), Item::Num(9)]))),
+            list("[3,9]")
+        );
+        assert_eq!(
+            Ok((
+                "",
+                Item::List(vec![
+                    Item::List(vec![Item::Num(42)]),
+                    Item::Num(1),
+                    Item::Num(2)
+                ])
+            )),
+            list("[[42],1,2]")
+        );
+    }
+
+    #[test]
+    fn list_limits_depth() {
+        let deepest = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        assert!(list(&deepest).is_ok());
+        let too_deep = "[".repeat(100_000);
+        let Err(nom::Err::Failure(err)) = list(&too_deep) else {
+            panic!("expected a failure");
+        };
+        assert_eq!(
+            (too_deep.len() - MAX_DEPTH, nom::error::ErrorKind::TooLarge),
+            (err.input.len(), err.code)
+        );
+    }
+
+    #[test]
+    fn ord_works() {
+        // 0 < 1
+        assert!(Item::Num(0) < Item::Num(1));
+        // 1 == 1
+        assert!(Item::Num(1) == Item::Num(1));
+        // 1 > 0
+        assert!(Item::Num(1) > Item::Num(0));
+        // [] < [1]
+        assert!(Item::List(vec![]) < Item::List(vec![Item::Num(1)]));
+        // [0] < [1]
+        assert!(Item::List(vec![Item::Num(0)]) < Item::List(vec![Item::Num(1)]));
+        // [1] == [1]
+        assert!(Item::List(vec![Item::Num(1)]) == Item::List(vec![Item::Num(1)]));
+        // [1] > []
+        assert!(Item::List(vec![Item::Num(1)]) > Item::List(vec![]));
+        // 1 > [0]
+        assert!(Item::Num(1) > Item::List(vec![Item::Num(0)]));
+        // 0 > []
+        assert!(Item::Num(0) > Item::List(vec![]));
+        // 0 < [1]
+        assert!(Item::Num(0) < Item::List(vec![Item::Num(1)]));
+        // [1,2,3] > [1,2]
+        assert!(
+            Item::List(vec![Item::Num(1), Item::Num(2), Item::Num(3)])
+                > Item::List(vec![Item::Num(1), Item::Num(2)])
+        );
+        // [[[1]]] > [[[]]]
+        assert!(
+            Item::List(vec![Item::List(vec![Item::List(vec![Item::Num(1)])])])
+                > Item::List(vec![Item::List(vec![Item::List(vec![])])])
+        )
+    }
+
+    #[test]
+    fn divider_positions_works() {
+        let (_, packets) = packets(INPUT).unwrap();
+        assert_eq!(
+            vec![10, 14],
+            divider_positions(&packets, &puzzle_dividers())
+        );
+
+        let (_, first) = list("[[1]]").unwrap();
+        let (_, last) = list("[99]").unwrap();
+        let dividers = [last, first.clone(), first];
+        assert_eq!(vec![19, 4, 5], divider_positions(&packets, &dividers));
+        assert_eq!(vec![3, 1, 2], divider_positions(&[], &dividers));
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!("[]", Item::List(vec![]).to_string());
+        assert_eq!(
+            "[1,[2,3],[[]]]",
+            Item::List(vec![
+                Item::Num(1),
+                Item::List(vec![Item::Num(2), Item::Num(3)]),
+                Item::List(vec![Item::List(vec![])])
+            ])
+            .to_string()
+        );
+    }
+
+    /// Numbers or lists nested at most `depth` deep.
+    fn item(rng: &mut Rng, depth: usize) -> Item {
+        if depth == 0 || rng.ratio(1, 2) {
+            Item::Num(rng.u32())
+        } else {
+            Item::List(rng.vec(0..8, |rng| item(rng, depth - 1)))
+        }
+    }
+
+    /// Packets are always lists at the top level.
+    pub(crate) fn packet(rng: &mut Rng) -> Item {
+        Item::List(rng.vec(0..8, |rng| item(rng, 3)))
+    }
+
+    #[test]
+    fn display_round_trips() {
+        check(|rng| {
+            let packet = packet(rng);
+            let text = packet.to_string();
+            assert_eq!(Ok(("", packet)), list(&text));
+        });
+    }
+}
@@ -0,0 +1,31 @@
+//! The original allocating `Ord`, as a function that can't call the new one.
+
+// allocations and all
+#![allow(clippy::useless_vec)]
+
+use std::cmp::Ordering;
+
+use crate::Item;
+
+pub fn compare(left: &Item, right: &Item) -> Ordering {
+    match (left, right) {
+        (Item::Num(left_num), Item::Num(right_num)) => left_num.cmp(right_num),
+        (Item::List(left_list), Item::List(right_list)) => compare_lists(left_list, right_list),
+        (Item::Num(val), Item::List(right_list)) => {
+            compare_lists(&vec![Item::Num(*val)], right_list)
+        }
+        (Item::List(left_list), Item::Num(right_num)) => {
+            compare_lists(left_list, &vec![Item::Num(*right_num)])
+        }
+    }
+}
+
+fn compare_lists(left: &[Item], right: &[Item]) -> Ordering {
+    for (left, right) in left.iter().zip(right) {
+        match compare(left, right) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+    left.len().cmp(&right.len())
+}
@@ -0,0 +1,339 @@
+//! Reading packets a line at a time from any [`BufRead`], so files too big to
+//! hold in memory can still be ordered or counted.
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{divider_positions, list, Item, Pair, MAX_DEPTH};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    Unexpected(char),
+    UnexpectedEnd,
+    /// Lists nested more than [`MAX_DEPTH`] deep.
+    TooDeep,
+    /// The last packet of the file has nothing to be compared with.
+    Unpaired,
+    /// A blank line where a pair's packet should be.
+    MissingPacket,
+    /// Something other than a blank line after a pair.
+    MissingSeparator,
+}
+
+/// Why a packet couldn't be parsed, with 1-based line and column numbers.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::Unexpected(c) => write!(f, "unexpected {:?}", c),
+            ErrorKind::UnexpectedEnd => write!(f, "unexpected end of packet"),
+            ErrorKind::TooDeep => write!(f, "lists nested more than {} deep", MAX_DEPTH),
+            ErrorKind::Unpaired => write!(f, "packet has no pair"),
+            ErrorKind::MissingPacket => write!(f, "expected a packet"),
+            ErrorKind::MissingSeparator => write!(f, "expected a blank line between pairs"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> Self {
+        ReadError::Parse(err)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "{}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// Parses a whole line as one packet, reporting errors against `line_number`.
+fn parse_line(line_number: usize, line: &str) -> Result<Item, ParseError> {
+    let (rest, too_deep) = match list(line) {
+        Ok(("", packet)) => return Ok(packet),
+        Ok((rest, _)) => (rest, false),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            (err.input, err.code == nom::error::ErrorKind::TooLarge)
+        }
+        Err(nom::Err::Incomplete(_)) => ("", false),
+    };
+    Err(ParseError {
+        line: line_number,
+        column: line.len() - rest.len() + 1,
+        kind: match rest.chars().next() {
+            _ if too_deep => ErrorKind::TooDeep,
+            Some(c) => ErrorKind::Unexpected(c),
+            None => ErrorKind::UnexpectedEnd,
+        },
+    })
+}
+
+/// Yields one packet per non-blank line. Lines may end in `\n` or `\r\n`.
+pub struct PacketReader<R> {
+    reader: R,
+    /// Reused for every line, so memory use doesn't grow with the input.
+    buf: String,
+    line: usize,
+}
+
+impl<R: BufRead> PacketReader<R> {
+    pub fn new(reader: R) -> Self {
+        PacketReader {
+            reader,
+            buf: String::new(),
+            line: 0,
+        }
+    }
+
+    /// Groups the packets into pairs instead, which must be on consecutive
+    /// lines and separated by single blank lines, as in the puzzle input.
+    pub fn pairs(self) -> Pairs<R> {
+        Pairs(self)
+    }
+
+    /// The next line without its line ending, or `None` at the end.
+    fn read_line(&mut self) -> io::Result<Option<&str>> {
+        self.buf.clear();
+        if self.reader.read_line(&mut self.buf)? == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        Ok(Some(self.buf.trim_end()))
+    }
+
+    fn read_packet(&mut self) -> Result<Option<Item>, ReadError> {
+        loop {
+            let line_number = self.line + 1;
+            match self.read_line()? {
+                None => return Ok(None),
+                Some("") => {}
+                Some(line) => return Ok(Some(parse_line(line_number, line)?)),
+            }
+        }
+    }
+
+    /// The packet on the next line, which mustn't be blank unless only blank
+    /// lines are left.
+    fn read_pair_packet(&mut self) -> Result<Option<Item>, ReadError> {
+        let line_number = self.line + 1;
+        match self.read_line()? {
+            None => Ok(None),
+            Some("") => match self.read_packet()? {
+                None => Ok(None),
+                Some(_) => Err(error_at(line_number, ErrorKind::MissingPacket)),
+            },
+            Some(line) => Ok(Some(parse_line(line_number, line)?)),
+        }
+    }
+
+    fn error(&self, kind: ErrorKind) -> ReadError {
+        error_at(self.line, kind)
+    }
+}
+
+fn error_at(line: usize, kind: ErrorKind) -> ReadError {
+    ReadError::Parse(ParseError {
+        line,
+        column: 1,
+        kind,
+    })
+}
+
+impl<R: BufRead> Iterator for PacketReader<R> {
+    type Item = Result<Item, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_packet().transpose()
+    }
+}
+
+/// Yields the packets of a [`PacketReader`] two at a time.
+pub struct Pairs<R>(PacketReader<R>);
+
+impl<R: BufRead> Pairs<R> {
+    fn read_pair(&mut self) -> Result<Option<Pair>, ReadError> {
+        let Some(left) = self.0.read_pair_packet()? else {
+            return Ok(None);
+        };
+        let Some(right) = self.0.read_pair_packet()? else {
+            return Err(self.0.error(ErrorKind::Unpaired));
+        };
+        match self.0.read_line()? {
+            None | Some("") => Ok(Some((left, right))),
+            Some(_) => Err(self.0.error(ErrorKind::MissingSeparator)),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Pairs<R> {
+    type Item = Result<Pair, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_pair().transpose()
+    }
+}
+
+/// Part 1 straight from a reader: the sum of the 1-based indices of the pairs
+/// in the right order.
+pub fn read_ordered_pair_sum(reader: impl BufRead) -> Result<usize, ReadError> {
+    let mut sum = 0;
+    for (idx, pair) in PacketReader::new(reader).pairs().enumerate() {
+        let (left, right) = pair?;
+        if left <= right {
+            sum += idx + 1;
+        }
+    }
+    Ok(sum)
+}
+
+/// [`divider_positions`] straight from a reader, holding one packet at a time.
+pub fn read_divider_positions(
+    reader: impl BufRead,
+    dividers: &[Item],
+) -> Result<Vec<usize>, ReadError> {
+    let mut error = None;
+    let packets =
+        PacketReader::new(reader).map_while(|packet| packet.map_err(|err| error = Some(err)).ok());
+    let positions = divider_positions(packets, dividers);
+    error.map_or(Ok(positions), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+    use crate::{packets, pairs, part1, part2, puzzle_dividers};
+
+    #[test]
+    fn reads_packets_and_pairs() {
+        let (_, expected) = packets(INPUT).unwrap();
+        let read = PacketReader::new(INPUT.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(expected, read);
+
+        let (_, expected) = pairs(INPUT).unwrap();
+        let read = PacketReader::new(INPUT.as_bytes())
+            .pairs()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(expected, read);
+    }
+
+    #[test]
+    fn handles_crlf() {
+        let input = INPUT.replace('\n', "\r\n") + "\r\n";
+        let read = PacketReader::new(input.as_bytes())
+            .pairs()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(pairs(INPUT).unwrap().1, read);
+    }
+
+    #[test]
+    fn parse_line_errors() {
+        let error = |column, kind| {
+            Err(ParseError {
+                line: 4,
+                column,
+                kind,
+            })
+        };
+        assert_eq!(error(4, ErrorKind::UnexpectedEnd), parse_line(4, "[1,"));
+        assert_eq!(error(1, ErrorKind::Unexpected('1')), parse_line(4, "1,[2]"));
+        assert_eq!(error(4, ErrorKind::Unexpected('x')), parse_line(4, "[1]x"));
+        let too_deep = "[".repeat(MAX_DEPTH + 1);
+        assert_eq!(
+            error(MAX_DEPTH + 1, ErrorKind::TooDeep),
+            parse_line(4, &too_deep)
+        );
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let mut reader = PacketReader::new("[1]\n\n[2]\n[3,[\n".as_bytes());
+        assert!(matches!(reader.next(), Some(Ok(_))));
+        assert!(matches!(reader.next(), Some(Ok(_))));
+        let Some(Err(ReadError::Parse(err))) = reader.next() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(
+            "line 4, column 5: unexpected end of packet",
+            err.to_string()
+        );
+
+        let mut pairs = PacketReader::new("[1]\n[2]\n\n[3]\n".as_bytes()).pairs();
+        assert!(matches!(pairs.next(), Some(Ok(_))));
+        let Some(Err(ReadError::Parse(err))) = pairs.next() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((4, ErrorKind::Unpaired), (err.line, err.kind));
+        assert!(pairs.next().is_none());
+    }
+
+    #[test]
+    fn pairs_need_blank_line_separators() {
+        let first_error = |input: &str| {
+            PacketReader::new(input.as_bytes())
+                .pairs()
+                .find_map(|pair| pair.err())
+                .map(|err| err.to_string())
+        };
+        assert_eq!(None, first_error("[1]\n[2]\n\n[3]\n[4]\n"));
+        assert_eq!(None, first_error("[1]\n[2]\n\n\n"));
+        assert_eq!(
+            Some("line 3, column 1: expected a blank line between pairs".to_string()),
+            first_error("[1]\n[2]\n[3]\n[4]\n")
+        );
+        assert_eq!(
+            Some("line 2, column 1: expected a packet".to_string()),
+            first_error("[1]\n\n[2]\n")
+        );
+        assert_eq!(
+            Some("line 4, column 1: expected a packet".to_string()),
+            first_error("[1]\n[2]\n\n\n[3]\n[4]\n")
+        );
+    }
+
+    #[test]
+    fn answers_from_reader() {
+        assert_eq!(
+            part1(INPUT),
+            read_ordered_pair_sum(INPUT.as_bytes()).unwrap()
+        );
+        let positions = read_divider_positions(INPUT.as_bytes(), &puzzle_dividers()).unwrap();
+        assert_eq!(part2(INPUT), positions.iter().product::<usize>());
+
+        let err = read_divider_positions("[1]\n[2,]\n".as_bytes(), &puzzle_dividers());
+        assert_eq!(
+            "line 2, column 4: unexpected ']'",
+            err.unwrap_err().to_string()
+        );
+    }
+}
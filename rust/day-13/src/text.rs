@@ -0,0 +1,260 @@
+//! Ordering packets straight from their text, without building [`Item`]s.
+//!
+//! [`Item`]: crate::Item
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::Side;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Num(u32),
+}
+
+/// Why [`compare_text`] couldn't read one of the packets, with a 1-based
+/// column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextError {
+    Unexpected {
+        side: Side,
+        column: usize,
+        c: char,
+    },
+    /// A number too large for a `u32`, starting at `column`.
+    TooLarge {
+        side: Side,
+        column: usize,
+    },
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let side = |side| match side {
+            Side::Left => "left",
+            Side::Right => "right",
+        };
+        match *self {
+            TextError::Unexpected { side: s, column, c } => {
+                write!(
+                    f,
+                    "{} packet, column {}: unexpected {:?}",
+                    side(s),
+                    column,
+                    c
+                )
+            }
+            TextError::TooLarge { side: s, column } => {
+                write!(f, "{} packet, column {}: number too large", side(s), column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
+/// The tokens of one packet, with room to pretend a number was written as a
+/// list holding just that number.
+struct Tokens<'a> {
+    side: Side,
+    text: &'a str,
+    /// Byte offset of the next character to read.
+    pos: usize,
+    /// A number to hand out again before reading on.
+    promoted: Option<u32>,
+    /// Closing brackets to hand out after `promoted`.
+    closes: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(side: Side, text: &'a str) -> Self {
+        Tokens {
+            side,
+            text,
+            pos: 0,
+            promoted: None,
+            closes: 0,
+        }
+    }
+
+    /// Turns the number just read into `[num]`. The caller has already
+    /// matched the opening bracket, so what's left is the number and a `]`.
+    fn promote(&mut self, num: u32) {
+        self.promoted = Some(num);
+        self.closes += 1;
+    }
+
+    /// The 1-based column, in characters, of the byte at `offset`.
+    fn column(&self, offset: usize) -> usize {
+        self.text[..offset].chars().count() + 1
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, TextError> {
+        if let Some(num) = self.promoted.take() {
+            return Ok(Some(Token::Num(num)));
+        }
+        if self.closes > 0 {
+            self.closes -= 1;
+            return Ok(Some(Token::Close));
+        }
+        let bytes = self.text.as_bytes();
+        loop {
+            let Some(&byte) = bytes.get(self.pos) else {
+                return Ok(None);
+            };
+            let start = self.pos;
+            self.pos += 1;
+            match byte {
+                b'[' => return Ok(Some(Token::Open)),
+                b']' => return Ok(Some(Token::Close)),
+                b',' => {}
+                b'0'..=b'9' => {
+                    let mut num = (byte - b'0') as u32;
+                    while let Some(&digit @ b'0'..=b'9') = bytes.get(self.pos) {
+                        num = num
+                            .checked_mul(10)
+                            .and_then(|num| num.checked_add((digit - b'0') as u32))
+                            .ok_or(TextError::TooLarge {
+                                side: self.side,
+                                column: self.column(start),
+                            })?;
+                        self.pos += 1;
+                    }
+                    return Ok(Some(Token::Num(num)));
+                }
+                _ => {
+                    return Err(TextError::Unexpected {
+                        side: self.side,
+                        column: self.column(start),
+                        c: self.text[start..].chars().next().unwrap(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Orders two packets written like `[1,[2,3]]` the same way [`Ord`] orders
+/// the parsed items, reading each side at most once and without allocating.
+///
+/// Fails on characters that can't be in a packet and on numbers too large for
+/// a `u32`, but only if it reaches them: it stops as soon as the order is
+/// decided, and doesn't check that brackets balance.
+pub fn compare_text(left: &str, right: &str) -> Result<Ordering, TextError> {
+    let mut left = Tokens::new(Side::Left, left);
+    let mut right = Tokens::new(Side::Right, right);
+    loop {
+        match (left.next()?, right.next()?) {
+            (None, None) => return Ok(Ordering::Equal),
+            (Some(Token::Num(left_num)), Some(Token::Num(right_num))) => {
+                if left_num != right_num {
+                    return Ok(left_num.cmp(&right_num));
+                }
+            }
+            (Some(Token::Open), Some(Token::Open)) | (Some(Token::Close), Some(Token::Close)) => {}
+            // whichever list ends first is smaller
+            (Some(Token::Close), _) | (None, _) => return Ok(Ordering::Less),
+            (_, Some(Token::Close)) | (_, None) => return Ok(Ordering::Greater),
+            (Some(Token::Num(num)), Some(Token::Open)) => left.promote(num),
+            (Some(Token::Open), Some(Token::Num(num))) => right.promote(num),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{packet, INPUT};
+    use crate::{naive, packets};
+    use aoc_check::check;
+
+    #[test]
+    fn compare_text_works() {
+        let lines = INPUT.lines().collect::<Vec<_>>();
+        let in_order = lines
+            .chunks(3)
+            .map(|pair| compare_text(pair[0], pair[1]).unwrap().is_lt())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![true, true, false, true, false, true, false, false],
+            in_order
+        );
+    }
+
+    #[test]
+    fn promotes_nested_numbers() {
+        assert_eq!(Ordering::Equal, compare_text("[[[[5]]]]", "[5]").unwrap());
+        assert_eq!(Ordering::Less, compare_text("[[[5]],1]", "[5,2]").unwrap());
+        assert_eq!(Ordering::Less, compare_text("[5]", "[[[[5],0]]]").unwrap());
+        assert_eq!(Ordering::Greater, compare_text("[10]", "[[9,99]]").unwrap());
+    }
+
+    #[test]
+    fn sorts_like_items() {
+        let (_, items) = packets(INPUT).unwrap();
+        let mut lines = INPUT
+            .lines()
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        lines.sort_by(|left, right| compare_text(left, right).unwrap());
+        let mut sorted = items.clone();
+        sorted.sort();
+        assert_eq!(
+            sorted
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>(),
+            lines
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(TextError::Unexpected {
+                side: Side::Right,
+                column: 4,
+                c: 'é'
+            }),
+            compare_text("[1,2]", "[1,é]")
+        );
+        assert_eq!(
+            Err(TextError::TooLarge {
+                side: Side::Left,
+                column: 2
+            }),
+            compare_text("[4294967296]", "[1]")
+        );
+        assert_eq!(
+            "left packet, column 2: number too large",
+            compare_text("[4294967296]", "[1]").unwrap_err().to_string()
+        );
+        assert_eq!(Ok(Ordering::Greater), compare_text("[4294967295]", "[1]"));
+        // nothing after the deciding token is read
+        assert_eq!(Ok(Ordering::Less), compare_text("[1,?]", "[2]"));
+    }
+
+    #[test]
+    fn matches_ord() {
+        check(|rng| {
+            let (left, right) = (packet(rng), packet(rng));
+            assert_eq!(
+                Ok(left.cmp(&right)),
+                compare_text(&left.to_string(), &right.to_string())
+            );
+        });
+    }
+
+    #[test]
+    fn matches_naive() {
+        check(|rng| {
+            let (left, right) = (packet(rng), packet(rng));
+            assert_eq!(
+                Ok(naive::compare(&left, &right)),
+                compare_text(&left.to_string(), &right.to_string())
+            );
+        });
+    }
+}